@@ -1,2 +1,8 @@
-RUSTFLAGS='-C link-arg=-s' cargo build --target wasm32-unknown-unknown --release
-cp target/wasm32-unknown-unknown/release/ft.wasm ./res/ft.wasm
+#!/bin/bash
+set -e
+
+# Rust 1.82+ enables post-MVP Wasm features (bulk-memory, sign-ext, ...) which the NEAR runtime rejects,
+# the contract code is built for the MVP CPU and wasm-opt lowers the features left by the prebuilt std
+RUSTFLAGS='-C link-arg=-s -C target-cpu=mvp' cargo build --target wasm32-unknown-unknown --release
+wasm-opt -Oz --signext-lowering --llvm-memory-copy-fill-lowering --llvm-nontrapping-fptoint-lowering \
+    target/wasm32-unknown-unknown/release/ft.wasm -o ./res/ft.wasm
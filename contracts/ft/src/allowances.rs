@@ -0,0 +1,439 @@
+use crate::*;
+
+/// Tokens an owner allows a spender to transfer on its behalf with ft_transfer_from()
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Allowance {
+    pub amount: Balance,
+
+    /// Block timestamp in nanoseconds from which the allowance can't be used, None means it never expires
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AllowanceView {
+    pub amount: U128,
+    pub expires_at: Option<U64>,
+}
+
+/// NEAR deposited by an account above the registration cost, it pays for the storage of the allowances it gives
+/// and of the Objectives rewarded to it
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct StorageAccount {
+    pub deposit: Balance,
+
+    /// Bytes used by the allowances and the reward records of the account
+    pub used_bytes: u64,
+
+    /// Spenders having an allowance from the account, so that they can be removed along with the account
+    pub spenders: Vec<AccountId>,
+}
+
+impl StorageAccount {
+    pub fn available(&self) -> Balance {
+        self.deposit
+            .saturating_sub(Balance::from(self.used_bytes) * env::storage_byte_cost())
+    }
+}
+
+impl FungibleToken {
+    /// Returns the allowance if it exists and hasn't expired
+    pub fn internal_allowance(
+        &self,
+        owner_id: &AccountId,
+        spender_id: &AccountId,
+    ) -> Option<Allowance> {
+        self.allowances
+            .get(&(owner_id.clone(), spender_id.clone()))
+            .filter(|allowance| {
+                allowance
+                    .expires_at
+                    .is_none_or(|expires_at| env::block_timestamp() < expires_at)
+            })
+    }
+
+    /// Sets or removes (None) the allowance, the storage it takes is paid from the owner's storage deposit
+    pub fn internal_set_allowance(
+        &mut self,
+        owner_id: &AccountId,
+        spender_id: &AccountId,
+        allowance: Option<Allowance>,
+    ) {
+        require!(
+            self.accounts.contains_key(owner_id),
+            "The account is not registered"
+        );
+        require!(
+            owner_id != spender_id,
+            "Owner and spender should be different"
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let key = (owner_id.clone(), spender_id.clone());
+        let mut storage_account = self.storage_accounts.get(owner_id).unwrap_or_default();
+
+        match allowance {
+            Some(allowance) => {
+                if self.allowances.insert(&key, &allowance).is_none() {
+                    storage_account.spenders.push(spender_id.clone());
+                }
+            }
+            None => {
+                if self.allowances.remove(&key).is_some() {
+                    storage_account.spenders.retain(|id| id != spender_id);
+                }
+            }
+        }
+
+        if storage_account.spenders.is_empty() && storage_account.deposit == 0 {
+            self.storage_accounts.remove(owner_id);
+            return;
+        }
+
+        // used_bytes is a fixed size field, so rewriting the account after measuring doesn't change the usage
+        self.storage_accounts.insert(owner_id, &storage_account);
+        storage_account.used_bytes = (storage_account.used_bytes + env::storage_usage())
+            .saturating_sub(initial_storage_usage);
+
+        require!(
+            storage_account.deposit
+                >= Balance::from(storage_account.used_bytes) * env::storage_byte_cost(),
+            "Not enough storage deposit for the allowance, top it up with storage_deposit"
+        );
+
+        self.storage_accounts.insert(owner_id, &storage_account);
+    }
+
+    /// Returns true if the storage deposit of the account can pay for bytes more
+    pub fn can_use_storage(&self, account_id: &AccountId, bytes: StorageUsage) -> bool {
+        let storage_account = self.storage_accounts.get(account_id).unwrap_or_default();
+
+        storage_account.available() >= Balance::from(bytes) * env::storage_byte_cost()
+    }
+
+    /// Pays for bytes more from the storage deposit of the account, they are freed only when it unregisters
+    pub fn internal_use_storage(&mut self, account_id: &AccountId, bytes: StorageUsage) {
+        require!(
+            self.can_use_storage(account_id, bytes),
+            "Not enough storage deposit for the reward records, top it up with storage_deposit"
+        );
+
+        let mut storage_account = self.storage_accounts.get(account_id).unwrap_or_default();
+        storage_account.used_bytes += bytes;
+        self.storage_accounts.insert(account_id, &storage_account);
+    }
+
+    /// Removes every allowance given by the account, returns its storage deposit
+    pub fn internal_remove_allowances(&mut self, owner_id: &AccountId) -> Balance {
+        match self.storage_accounts.remove(owner_id) {
+            Some(storage_account) => {
+                for spender_id in storage_account.spenders {
+                    self.allowances.remove(&(owner_id.clone(), spender_id));
+                }
+                storage_account.deposit
+            }
+            None => 0,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Allow the spender to transfer up to amount tokens of the caller, replacing the previous allowance
+    ///
+    /// Storage of a new allowance is paid from the caller's storage deposit, Wallet UX Security -> Attach 1 Yocto
+    #[payable]
+    pub fn ft_approve(
+        &mut self,
+        spender_id: ValidAccountId,
+        amount: U128,
+        expires_at: Option<U64>,
+    ) {
+        assert_one_yocto();
+        require!(amount.0 > 0, "The amount should be a positive number");
+
+        let owner_id = env::predecessor_account_id();
+        let spender_id: AccountId = spender_id.into();
+
+        self.token.internal_set_allowance(
+            &owner_id,
+            &spender_id,
+            Some(Allowance {
+                amount: amount.into(),
+                expires_at: expires_at.map(|expires_at| expires_at.into()),
+            }),
+        );
+
+        AllowanceUpdateLog {
+            owner_id,
+            spender_id,
+            amount,
+            expires_at,
+        }
+        .emit();
+    }
+
+    /// Increase the allowance of the spender, keeping its expiry
+    ///
+    /// Wallet UX Security -> Attach 1 Yocto
+    #[payable]
+    pub fn ft_increase_allowance(&mut self, spender_id: ValidAccountId, amount: U128) {
+        assert_one_yocto();
+
+        let owner_id = env::predecessor_account_id();
+        let spender_id: AccountId = spender_id.into();
+
+        let mut allowance = self
+            .token
+            .internal_allowance(&owner_id, &spender_id)
+            .unwrap_or_else(|| env::panic(b"Allowance doesn't exist"));
+        allowance.amount = allowance
+            .amount
+            .checked_add(amount.into())
+            .unwrap_or_else(|| env::panic(b"Allowance overflow"));
+
+        self.internal_update_allowance(owner_id, spender_id, allowance);
+    }
+
+    /// Decrease the allowance of the spender, an allowance decreased to zero is removed
+    ///
+    /// Wallet UX Security -> Attach 1 Yocto
+    #[payable]
+    pub fn ft_decrease_allowance(&mut self, spender_id: ValidAccountId, amount: U128) {
+        assert_one_yocto();
+
+        let owner_id = env::predecessor_account_id();
+        let spender_id: AccountId = spender_id.into();
+
+        let mut allowance = self
+            .token
+            .internal_allowance(&owner_id, &spender_id)
+            .unwrap_or_else(|| env::panic(b"Allowance doesn't exist"));
+        allowance.amount = allowance
+            .amount
+            .checked_sub(amount.into())
+            .unwrap_or_else(|| env::panic(b"Allowance can't go below zero"));
+
+        self.internal_update_allowance(owner_id, spender_id, allowance);
+    }
+
+    /// Remove the allowance of the spender, its storage is credited back to the caller's storage deposit
+    ///
+    /// Wallet UX Security -> Attach 1 Yocto
+    #[payable]
+    pub fn ft_revoke(&mut self, spender_id: ValidAccountId) {
+        assert_one_yocto();
+
+        let owner_id = env::predecessor_account_id();
+        let spender_id: AccountId = spender_id.into();
+
+        require!(
+            self.token
+                .allowances
+                .contains_key(&(owner_id.clone(), spender_id.clone())),
+            "Allowance doesn't exist"
+        );
+        self.token
+            .internal_set_allowance(&owner_id, &spender_id, None);
+
+        AllowanceRevokeLog {
+            owner_id,
+            spender_id,
+        }
+        .emit();
+    }
+
+    /// Transfer tokens of the owner to the receiver, using the allowance the owner gave to the caller
+    ///
+    /// Wallet UX Security -> Attach 1 Yocto
+    #[payable]
+    pub fn ft_transfer_from(
+        &mut self,
+        owner_id: ValidAccountId,
+        receiver_id: ValidAccountId,
+        amount: U128,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+        self.assert_not_paused(PauseFlag::Transfers);
+
+        let owner_id: AccountId = owner_id.into();
+        let spender_id = env::predecessor_account_id();
+        let amount: Balance = amount.into();
+
+        let mut allowance = self
+            .token
+            .internal_allowance(&owner_id, &spender_id)
+            .unwrap_or_else(|| env::panic(b"Allowance doesn't exist or has expired"));
+        require!(
+            allowance.amount >= amount,
+            "Allowance is not enough for the transfer"
+        );
+        allowance.amount -= amount;
+
+        self.token
+            .internal_transfer(&owner_id, receiver_id.as_ref(), amount, memo);
+        self.internal_update_allowance(owner_id, spender_id, allowance);
+    }
+
+    /// View Function - returns the allowance the owner gave to the spender, None if it doesn't exist or has expired
+    pub fn ft_allowance(
+        &self,
+        owner_id: ValidAccountId,
+        spender_id: ValidAccountId,
+    ) -> Option<AllowanceView> {
+        self.token
+            .internal_allowance(owner_id.as_ref(), spender_id.as_ref())
+            .map(|allowance| AllowanceView {
+                amount: U128::from(allowance.amount),
+                expires_at: allowance.expires_at.map(U64::from),
+            })
+    }
+}
+
+impl Contract {
+    /// Stores the new amount of the allowance, removing it once it reaches zero, and logs the change
+    fn internal_update_allowance(
+        &mut self,
+        owner_id: AccountId,
+        spender_id: AccountId,
+        allowance: Allowance,
+    ) {
+        let (amount, expires_at) = (allowance.amount, allowance.expires_at);
+
+        if amount == 0 {
+            self.token
+                .internal_set_allowance(&owner_id, &spender_id, None);
+        } else {
+            self.token
+                .internal_set_allowance(&owner_id, &spender_id, Some(allowance));
+        }
+
+        AllowanceUpdateLog {
+            owner_id,
+            spender_id,
+            amount: U128::from(amount),
+            expires_at: expires_at.map(U64::from),
+        }
+        .emit();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod allowances_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    /// carol has 1000 tokens and a storage deposit for allowances, alice is registered
+    fn create_allowance_contract() -> Contract {
+        testing_env!(get_context(dex().to_string(), 0));
+        let mut contract = create_contract();
+
+        let registration_cost = contract.storage_balance_bounds().min.0;
+        testing_env!(get_context(dex().to_string(), registration_cost));
+        contract.storage_deposit(Some(carol()));
+        contract.storage_deposit(Some(alice()));
+
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+        contract.storage_deposit(Some(carol()));
+
+        testing_env!(get_context(dex().to_string(), ONE_YOCTO));
+        contract.ft_transfer(carol(), U128::from(1000), None);
+
+        contract
+    }
+
+    #[test]
+    fn ft_transfer_from() {
+        let mut contract = create_allowance_contract();
+
+        at(carol(), ONE_YOCTO, 0);
+        contract.ft_approve(bob(), U128::from(600), Some(U64::from(100)));
+
+        at(bob(), ONE_YOCTO, 10);
+        contract.ft_transfer_from(carol(), alice(), U128::from(400), None);
+
+        assert_eq!(contract.ft_balance_of(carol()).0, 600);
+        assert_eq!(contract.ft_balance_of(alice()).0, 400);
+        assert_eq!(
+            contract.ft_allowance(carol(), bob()),
+            Some(AllowanceView {
+                amount: U128::from(200),
+                expires_at: Some(U64::from(100)),
+            })
+        );
+
+        // Allowance used up is removed
+        contract.ft_transfer_from(carol(), alice(), U128::from(200), None);
+        assert_eq!(contract.ft_allowance(carol(), bob()), None);
+        assert!(contract
+            .token
+            .storage_accounts
+            .get(&carol().into())
+            .unwrap()
+            .spenders
+            .is_empty());
+    }
+
+    #[test]
+    fn increase_decrease_and_revoke() {
+        let mut contract = create_allowance_contract();
+
+        at(carol(), ONE_YOCTO, 0);
+        contract.ft_approve(bob(), U128::from(600), None);
+        let available = contract.storage_balance_of(carol()).unwrap().available.0;
+        assert!(available < STORAGE_COST);
+
+        contract.ft_increase_allowance(bob(), U128::from(100));
+        contract.ft_decrease_allowance(bob(), U128::from(300));
+        assert_eq!(
+            contract.ft_allowance(carol(), bob()).unwrap().amount,
+            U128::from(400)
+        );
+
+        // Storage of the revoked allowance is credited back
+        contract.ft_revoke(bob());
+        assert_eq!(contract.ft_allowance(carol(), bob()), None);
+        assert_eq!(
+            contract.storage_balance_of(carol()).unwrap().available.0,
+            STORAGE_COST
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Allowance doesn't exist or has expired")]
+    fn ft_transfer_from_expired() {
+        let mut contract = create_allowance_contract();
+
+        at(carol(), ONE_YOCTO, 0);
+        contract.ft_approve(bob(), U128::from(600), Some(U64::from(100)));
+
+        at(bob(), ONE_YOCTO, 100);
+        contract.ft_transfer_from(carol(), alice(), U128::from(400), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Allowance is not enough for the transfer")]
+    fn ft_transfer_from_beyond_allowance() {
+        let mut contract = create_allowance_contract();
+
+        at(carol(), ONE_YOCTO, 0);
+        contract.ft_approve(bob(), U128::from(600), None);
+
+        at(bob(), ONE_YOCTO, 0);
+        contract.ft_transfer_from(carol(), alice(), U128::from(601), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough storage deposit for the allowance")]
+    fn ft_approve_without_storage_deposit() {
+        let mut contract = create_allowance_contract();
+
+        at(alice(), ONE_YOCTO, 0);
+        contract.ft_approve(bob(), U128::from(600), None);
+    }
+}
@@ -0,0 +1,427 @@
+use crate::*;
+
+/// Max depth of a campaign Merkle tree, enough for 2^32 leaves
+const MAX_PROOF_LENGTH: usize = 32;
+
+/// Prefixes used while hashing, so that a node can never be passed off as a leaf
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// Stable identifier of a reward campaign, its position in the campaigns list
+pub type CampaignId = u32;
+
+/// Rewards computed off-chain and published as a Merkle root, claimed by the players themselves
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Campaign {
+    /// Root of the Merkle tree of (index, account_id, amount) leaves
+    pub merkle_root: Vec<u8>,
+
+    /// Tokens reserved from the reward pool for the campaign
+    pub budget: Balance,
+
+    /// Tokens claimed so far
+    pub claimed: Balance,
+
+    /// Block timestamp in nanoseconds after which leaves can't be claimed anymore
+    pub expires_at: u64,
+
+    /// True once the unclaimed tokens are returned to the reward pool
+    pub closed: bool,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CampaignView {
+    pub id: CampaignId,
+    pub merkle_root: Base64VecU8,
+    pub budget: U128,
+    pub claimed: U128,
+    pub expires_at: U64,
+    pub closed: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct RewardCampaigns {
+    pub campaigns: Vector<Campaign>,
+
+    /// (Campaign, index / 128) -> Bitmap of the claimed leaves
+    pub claimed_bitmap: LookupMap<(CampaignId, u64), u128>,
+}
+
+impl Default for RewardCampaigns {
+    fn default() -> Self {
+        Self {
+            campaigns: Vector::new(StorageKey::Campaigns.try_to_vec().unwrap()),
+            claimed_bitmap: LookupMap::new(StorageKey::CampaignClaims.try_to_vec().unwrap()),
+        }
+    }
+}
+
+/// sha256(0x00 || index as u64 LE || amount as u128 LE || account_id)
+pub fn campaign_leaf(index: u64, account_id: &str, amount: Balance) -> Vec<u8> {
+    let mut leaf = vec![LEAF_PREFIX];
+    leaf.extend_from_slice(&index.to_le_bytes());
+    leaf.extend_from_slice(&amount.to_le_bytes());
+    leaf.extend_from_slice(account_id.as_bytes());
+
+    env::sha256(&leaf)
+}
+
+/// sha256(0x01 || left || right)
+pub fn campaign_node(left: &[u8], right: &[u8]) -> Vec<u8> {
+    let mut node = vec![NODE_PREFIX];
+    node.extend_from_slice(left);
+    node.extend_from_slice(right);
+
+    env::sha256(&node)
+}
+
+impl RewardCampaigns {
+    pub fn get_campaign(&self, campaign_id: CampaignId) -> Campaign {
+        self.campaigns
+            .get(campaign_id.into())
+            .unwrap_or_else(|| env::panic(b"Invalid Campaign"))
+    }
+
+    pub fn is_claimed(&self, campaign_id: CampaignId, index: u64) -> bool {
+        let bitmap = self
+            .claimed_bitmap
+            .get(&(campaign_id, index / 128))
+            .unwrap_or(0);
+
+        bitmap & (1 << (index % 128)) != 0
+    }
+
+    fn set_claimed(&mut self, campaign_id: CampaignId, index: u64) {
+        let key = (campaign_id, index / 128);
+        let bitmap = self.claimed_bitmap.get(&key).unwrap_or(0);
+
+        self.claimed_bitmap
+            .insert(&key, &(bitmap | (1 << (index % 128))));
+    }
+}
+
+impl Campaign {
+    /// Siblings are hashed on the left or right as per the bits of the index, starting from the leaf level
+    pub fn verify_proof(&self, index: u64, leaf: Vec<u8>, proof: &[Base64VecU8]) -> bool {
+        let mut node = leaf;
+
+        for (level, sibling) in proof.iter().enumerate() {
+            require!(sibling.0.len() == 32, "Proof hashes have to be 32 bytes");
+
+            node = if (index >> level) & 1 == 0 {
+                campaign_node(&node, &sibling.0)
+            } else {
+                campaign_node(&sibling.0, &node)
+            };
+        }
+
+        node == self.merkle_root
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Admin Only - Publish a reward campaign, its budget is reserved from the reward pool
+    ///
+    /// returns id of the new campaign
+    pub fn create_campaign(
+        &mut self,
+        merkle_root: Base64VecU8,
+        budget: U128,
+        expires_at: U64,
+    ) -> CampaignId {
+        self.assert_role(Role::Admin);
+
+        let budget: Balance = budget.into();
+
+        require!(merkle_root.0.len() == 32, "Merkle root has to be 32 bytes");
+        require!(budget > 0, "The budget should be a positive number");
+        require!(
+            expires_at.0 > env::block_timestamp(),
+            "Campaign should expire in the future"
+        );
+        require!(
+            self.campaigns.campaigns.len() <= u64::from(CampaignId::MAX),
+            "Maximum number of Campaigns reached"
+        );
+        require!(
+            self.treasury.balance >= budget,
+            "Reward pool doesn't have enough balance"
+        );
+
+        self.treasury.balance -= budget;
+
+        self.campaigns.campaigns.push(&Campaign {
+            merkle_root: merkle_root.0,
+            budget,
+            claimed: 0,
+            expires_at: expires_at.0,
+            closed: false,
+        });
+
+        let campaign_id = (self.campaigns.campaigns.len() - 1) as CampaignId;
+
+        CampaignCreateLog {
+            campaign_id,
+            budget: U128::from(budget),
+            expires_at,
+        }
+        .emit();
+
+        campaign_id
+    }
+
+    /// Claim the caller's leaf of a campaign, proof has the sibling hashes from the leaf level up to the root
+    pub fn claim_campaign_reward(
+        &mut self,
+        campaign_id: CampaignId,
+        index: u64,
+        amount: U128,
+        proof: Vec<Base64VecU8>,
+    ) -> U128 {
+        self.assert_not_paused(PauseFlag::Rewards);
+
+        let account_id = env::predecessor_account_id();
+        let amount: Balance = amount.into();
+
+        self.assert_kyc_verified(&account_id);
+        self.token.assert_can_receive(&account_id);
+
+        let mut campaign = self.campaigns.get_campaign(campaign_id);
+
+        require!(
+            !campaign.closed && env::block_timestamp() < campaign.expires_at,
+            "Campaign has expired"
+        );
+        require!(
+            !self.campaigns.is_claimed(campaign_id, index),
+            "Reward is already claimed"
+        );
+        require!(proof.len() <= MAX_PROOF_LENGTH, "Proof is too long");
+        require!(
+            campaign.verify_proof(index, campaign_leaf(index, &account_id, amount), &proof),
+            "Invalid Merkle proof"
+        );
+        require!(
+            campaign.claimed + amount <= campaign.budget,
+            "Campaign budget exhausted"
+        );
+
+        self.campaigns.set_claimed(campaign_id, index);
+        campaign.claimed += amount;
+        self.campaigns
+            .campaigns
+            .replace(campaign_id.into(), &campaign);
+
+        self.token.internal_transfer(
+            &env::current_account_id(),
+            &account_id,
+            amount,
+            Some("campaign".to_string()),
+        );
+
+        CampaignClaimLog {
+            campaign_id,
+            index,
+            account_id,
+            amount: U128::from(amount),
+        }
+        .emit();
+
+        U128::from(amount)
+    }
+
+    /// Return the unclaimed tokens of an expired campaign to the reward pool, anyone can call it
+    pub fn close_campaign(&mut self, campaign_id: CampaignId) -> U128 {
+        let mut campaign = self.campaigns.get_campaign(campaign_id);
+
+        require!(!campaign.closed, "Campaign is already closed");
+        require!(
+            env::block_timestamp() >= campaign.expires_at,
+            "Campaign hasn't expired yet"
+        );
+
+        let unclaimed = campaign.budget - campaign.claimed;
+
+        campaign.closed = true;
+        self.campaigns
+            .campaigns
+            .replace(campaign_id.into(), &campaign);
+
+        self.treasury.balance += unclaimed;
+
+        CampaignCloseLog {
+            campaign_id,
+            unclaimed: U128::from(unclaimed),
+        }
+        .emit();
+
+        U128::from(unclaimed)
+    }
+
+    /// View Function - returns the campaign
+    pub fn get_campaign(&self, campaign_id: CampaignId) -> CampaignView {
+        let campaign = self.campaigns.get_campaign(campaign_id);
+
+        CampaignView {
+            id: campaign_id,
+            merkle_root: Base64VecU8::from(campaign.merkle_root),
+            budget: U128::from(campaign.budget),
+            claimed: U128::from(campaign.claimed),
+            expires_at: U64::from(campaign.expires_at),
+            closed: campaign.closed,
+        }
+    }
+
+    /// View Function - returns true if the leaf of the campaign is already claimed
+    pub fn is_campaign_reward_claimed(&self, campaign_id: CampaignId, index: u64) -> bool {
+        self.campaigns.get_campaign(campaign_id);
+        self.campaigns.is_claimed(campaign_id, index)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod campaigns_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::Balance;
+    use near_sdk::MockedBlockchain;
+
+    /// Leaves of the test campaign -> (index, account, amount)
+    fn leaves() -> Vec<(u64, ValidAccountId, Balance)> {
+        vec![(0, alice(), 100), (1, bob(), 200), (2, carol(), 300)]
+    }
+
+    /// Merkle root and the proof of every leaf, odd levels duplicate their last node
+    fn merkle_tree() -> (Vec<u8>, Vec<Vec<Base64VecU8>>) {
+        let hashes: Vec<Vec<u8>> = leaves()
+            .iter()
+            .map(|(index, account_id, amount)| campaign_leaf(*index, account_id.as_ref(), *amount))
+            .collect();
+
+        let (n0, n1, n2) = (&hashes[0], &hashes[1], &hashes[2]);
+        let left = campaign_node(n0, n1);
+        let right = campaign_node(n2, n2);
+        let root = campaign_node(&left, &right);
+
+        let proof = |hashes: Vec<&Vec<u8>>| {
+            hashes
+                .into_iter()
+                .map(|hash| Base64VecU8::from(hash.clone()))
+                .collect()
+        };
+
+        (
+            root,
+            vec![
+                proof(vec![n1, &right]),
+                proof(vec![n0, &right]),
+                proof(vec![n2, &left]),
+            ],
+        )
+    }
+
+    /// alice, bob and carol are registered but not KYC verified
+    fn create_unverified_campaign_contract() -> (Contract, Vec<Vec<Base64VecU8>>) {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(alice()));
+        contract.storage_deposit(Some(bob()));
+        contract.storage_deposit(Some(carol()));
+        seed_reward_pool(&mut contract, 1000);
+
+        let (root, proofs) = merkle_tree();
+        contract.create_campaign(Base64VecU8::from(root), U128::from(600), U64::from(100));
+
+        (contract, proofs)
+    }
+
+    fn create_campaign_contract() -> (Contract, Vec<Vec<Base64VecU8>>) {
+        let (mut contract, proofs) = create_unverified_campaign_contract();
+        verify_players(&mut contract, &[alice(), bob(), carol()]);
+
+        (contract, proofs)
+    }
+
+    #[test]
+    fn claim_campaign_reward() {
+        let (mut contract, proofs) = create_campaign_contract();
+        assert_eq!(contract.get_reward_pool().balance.0, 400);
+
+        at(carol(), 0, 10);
+        contract.claim_campaign_reward(0, 2, U128::from(300), proofs[2].clone());
+
+        at(alice(), 0, 10);
+        contract.claim_campaign_reward(0, 0, U128::from(100), proofs[0].clone());
+
+        assert_eq!(contract.ft_balance_of(carol()).0, 300);
+        assert_eq!(contract.ft_balance_of(alice()).0, 100);
+        assert_eq!(contract.get_campaign(0).claimed.0, 400);
+        assert!(contract.is_campaign_reward_claimed(0, 2));
+        assert!(!contract.is_campaign_reward_claimed(0, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Account carol.near is not KYC verified")]
+    fn claim_campaign_reward_unverified() {
+        let (mut contract, proofs) = create_unverified_campaign_contract();
+
+        at(carol(), 0, 10);
+        contract.claim_campaign_reward(0, 2, U128::from(300), proofs[2].clone());
+    }
+
+    #[test]
+    #[should_panic(expected = "Reward is already claimed")]
+    fn claim_campaign_reward_twice() {
+        let (mut contract, proofs) = create_campaign_contract();
+
+        at(bob(), 0, 10);
+        contract.claim_campaign_reward(0, 1, U128::from(200), proofs[1].clone());
+        contract.claim_campaign_reward(0, 1, U128::from(200), proofs[1].clone());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Merkle proof")]
+    fn claim_campaign_reward_of_another_player() {
+        let (mut contract, proofs) = create_campaign_contract();
+
+        at(alice(), 0, 10);
+        contract.claim_campaign_reward(0, 1, U128::from(200), proofs[1].clone());
+    }
+
+    #[test]
+    #[should_panic(expected = "Campaign has expired")]
+    fn claim_campaign_reward_expired() {
+        let (mut contract, proofs) = create_campaign_contract();
+
+        at(bob(), 0, 100);
+        contract.claim_campaign_reward(0, 1, U128::from(200), proofs[1].clone());
+    }
+
+    #[test]
+    fn close_campaign() {
+        let (mut contract, proofs) = create_campaign_contract();
+
+        at(bob(), 0, 10);
+        contract.claim_campaign_reward(0, 1, U128::from(200), proofs[1].clone());
+
+        at(alice(), 0, 100);
+        assert_eq!(contract.close_campaign(0), U128::from(400));
+        assert_eq!(contract.get_reward_pool().balance.0, 800);
+        assert!(contract.get_campaign(0).closed);
+    }
+
+    #[test]
+    #[should_panic(expected = "Campaign hasn't expired yet")]
+    fn close_campaign_before_expiry() {
+        let (mut contract, _) = create_campaign_contract();
+
+        at(alice(), 0, 99);
+        contract.close_campaign(0);
+    }
+}
@@ -2,22 +2,29 @@ use crate::*;
 
 use std::convert::From;
 
+/// Gas kept aside for returning the result of transfer_batch_reward()
+const GAS_FOR_BATCH_RESULT: Gas = 10_000_000_000_000;
+
+/// Initial estimate of the gas used by a single request in transfer_batch_reward()
+const GAS_FOR_REWARD_REQUEST: Gas = 10_000_000_000_000;
+
 /************/
 /*   Note   */
 /************/
 
 // obj_metadata and stats is in the same order as objectives
 
-// Both of them should be validated before deploying the contract itself
+// Default Objectives should be validated before deploying the contract itself, later ones are validated by add_objective()
 
-/******************/
-/*   Assumption   */
-/******************/
+// Objectives are never removed or reordered, only retired, so the position of an Objective is its stable ObjectiveId
 
-// Number of Achievements will be <= 2^8
+/// Stable identifier of an Objective, it never changes once the Objective is added
+pub type ObjectiveId = u32;
 
 /// Denotes Rarity of a Objective according to how many of them are able to achieve it
-#[derive(BorshDeserialize, BorshSerialize, Serialize)]
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, Debug, PartialEq,
+)]
 #[serde(crate = "near_sdk::serde")]
 pub enum Rarity {
     Common,
@@ -35,11 +42,41 @@ pub struct CatchObjectives {
 
     /// Stats Regarding InGame Objectives
     pub stats: Vector<ObjectiveStats>,
+
+    /// Objectives already rewarded to a player -> block timestamp of the claim
+    pub player_objectives: LookupMap<AccountId, UnorderedMap<ObjectiveId, u64>>,
+
+    /// Player -> Nonce expected in the next reward call for the player
+    pub reward_nonces: LookupMap<AccountId, u64>,
+
+    /// Rarity Thresholds applicable to every Objective unless overridden
+    pub rarity_thresholds: RarityThresholds,
+
+    /// Objective specific Rarity Thresholds
+    pub obj_rarity_thresholds: LookupMap<ObjectiveId, RarityThresholds>,
+
+    /// Season id -> Season
+    pub seasons: UnorderedMap<String, Season>,
+
+    /// Quest line -> Objectives of the quest line
+    pub quest_lines: UnorderedMap<String, Vec<ObjectiveId>>,
+
+    /// Category -> Objectives in the category
+    pub categories: LabelIndex,
+
+    /// Tag -> Objectives having the tag
+    pub tags: LabelIndex,
+
+    /// Region code -> Objectives located in the region
+    pub regions: LabelIndex,
+
+    /// Cell of the Catch Map grid -> Objectives located in the cell, used by get_objectives_in_box()
+    pub cells: LabelIndex,
 }
 
-// Read Only
+// Editable only by Owner
 
-#[derive(BorshDeserialize, BorshSerialize, Serialize)]
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Debug, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
 pub struct Objective {
     /// Title name of the Objective
@@ -47,11 +84,55 @@ pub struct Objective {
 
     /// Amount of Catch Tokens to be awarded on completion of Objective
     pub reward: Balance,
+
+    /// Retired Objectives can't be rewarded anymore, but are kept for history
+    #[serde(default)]
+    pub retired: bool,
+
+    /// Block timestamp in nanoseconds from which the Objective can be rewarded, None means right away
+    #[serde(default)]
+    pub starts_at: Option<U64>,
+
+    /// Block timestamp in nanoseconds from which the Objective can't be rewarded anymore, None means never
+    #[serde(default)]
+    pub ends_at: Option<U64>,
+
+    /// Season the Objective belongs to, it can be rewarded only while the Season is open too
+    #[serde(default)]
+    pub season_id: Option<String>,
+
+    /// Objectives which have to be rewarded to the player before this one
+    #[serde(default)]
+    pub prerequisites: Vec<ObjectiveId>,
+
+    /// Quest line the Objective is part of
+    #[serde(default)]
+    pub quest_line: Option<String>,
+
+    /// Decides how the reward changes as more players achieve the Objective
+    #[serde(default)]
+    pub reward_curve: RewardCurve,
+
+    /// Rewards of the Objective are locked and vest as per the schedule, None pays them right away
+    #[serde(default)]
+    pub vesting: Option<VestingSchedule>,
+
+    /// Category the Objective is listed under in the Objective browser
+    #[serde(default)]
+    pub category: Option<String>,
+
+    /// Free-form tags of the Objective
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Place on the Catch Map where the Objective can be accomplished
+    #[serde(default)]
+    pub location: Option<GeoLocation>,
 }
 
-// Read Only
+// Editable only by Owner
 
-#[derive(BorshDeserialize, BorshSerialize, Serialize)]
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Debug, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
 pub struct ObjectiveMetadata {
     /// An IPFS link to JSON file containing detalis of the achievement [link,description,etc....]
@@ -63,7 +144,7 @@ pub struct ObjectiveMetadata {
 
 // Read and Write Both Allowed
 
-#[derive(BorshDeserialize, BorshSerialize, Serialize)]
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Debug, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
 pub struct ObjectiveStats {
     /// Number of people who have accomplished the Objective
@@ -73,18 +154,82 @@ pub struct ObjectiveStats {
     pub rarity: Rarity,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PlayerObjective {
+    pub obj_id: ObjectiveId,
+
+    /// Block timestamp at which the player was rewarded for the Objective
+    pub rewarded_at: U64,
+}
+
+/// Reward for a single player in transfer_batch_reward()
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PlayerRewardRequest {
+    pub player_id: AccountId,
+    pub obj_ids: Vec<ObjectiveId>,
+    pub nonce: U64,
+}
+
+/// Reward checked and computed by internal_plan_reward(), paid by internal_apply_reward()
+pub struct RewardPlan {
+    /// Reward nonce of the player consumed by the reward, None if the reward isn't nonce based
+    pub nonce: Option<u64>,
+
+    /// Objectives being rewarded along with their reward, in the same order as the requested obj_ids
+    pub rewards: Vec<(Objective, Balance)>,
+
+    /// Part of the rewards paid from the reward pool, sponsored Objectives are paid from their escrow
+    pub from_pool: Balance,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BatchRewardResult {
+    /// Receipts of the processed requests, in the same order as the requests
+    pub receipts: Vec<RewardReceipt>,
+
+    /// Index of the first request which was not processed, None if all of them were processed
+    pub next_index: Option<u32>,
+
+    /// Index and reason of the requests which were skipped, the other requests are still processed
+    pub failed: Vec<(u32, String)>,
+}
+
+/// Returned by transfer_player_reward() so that the NFT Contract callback can verify what was paid
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardReceipt {
+    pub player_id: AccountId,
+    pub obj_ids: Vec<ObjectiveId>,
+
+    /// Total amount of Catch Tokens paid
+    pub amount: U128,
+
+    /// Reward nonce of the player consumed by this reward
+    pub nonce: U64,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
 pub struct JsonObjective {
+    pub id: ObjectiveId,
+
     pub objective: Objective,
 
     pub obj_metadata: ObjectiveMetadata,
 
     pub stats: ObjectiveStats,
+
+    pub status: ObjectiveStatus,
+
+    /// Reward which the next winner of the Objective would get as per its Reward Curve
+    pub current_reward: U128,
 }
 
-impl CatchObjectives {
-    pub fn default() -> CatchObjectives {
+impl Default for CatchObjectives {
+    fn default() -> CatchObjectives {
         let mut objectives = Vector::new(StorageKey::Objective.try_to_vec().unwrap());
         objectives.extend(Objective::default_list());
 
@@ -100,35 +245,218 @@ impl CatchObjectives {
         let mut stats = Vector::new(StorageKey::ObjectiveStats.try_to_vec().unwrap());
         stats.extend(ObjectiveStats::default_list());
 
+        CatchObjectives::new(objectives, obj_metadata, stats)
+    }
+}
+
+impl CatchObjectives {
+    /// Wraps existing Objectives, their metadata and stats with empty reward records and indexes
+    pub fn new(
+        objectives: Vector<Objective>,
+        obj_metadata: LazyOption<Vector<ObjectiveMetadata>>,
+        stats: Vector<ObjectiveStats>,
+    ) -> CatchObjectives {
+        let player_objectives = LookupMap::new(StorageKey::PlayerObjectives.try_to_vec().unwrap());
+
+        let obj_rarity_thresholds =
+            LookupMap::new(StorageKey::ObjectiveRarityThresholds.try_to_vec().unwrap());
+
         CatchObjectives {
             objectives,
             obj_metadata,
             stats,
+            player_objectives,
+            reward_nonces: LookupMap::new(StorageKey::RewardNonces.try_to_vec().unwrap()),
+            rarity_thresholds: RarityThresholds::default(),
+            obj_rarity_thresholds,
+            seasons: UnorderedMap::new(StorageKey::Seasons.try_to_vec().unwrap()),
+            quest_lines: UnorderedMap::new(StorageKey::QuestLines.try_to_vec().unwrap()),
+            categories: LabelIndex::new(StorageKey::Categories),
+            tags: LabelIndex::new(StorageKey::Tags),
+            regions: LabelIndex::new(StorageKey::Regions),
+            cells: LabelIndex::new(StorageKey::GeoCells),
+        }
+    }
+
+    /// Appends a new Objective along with its metadata and stats, returns index of the new Objective
+    pub fn add_objective(
+        &mut self,
+        objective: &Objective,
+        obj_metadata: &ObjectiveMetadata,
+        stats: &ObjectiveStats,
+    ) -> ObjectiveId {
+        require!(
+            self.objectives.len() <= u64::from(ObjectiveId::MAX),
+            "Maximum number of Objectives reached"
+        );
+        obj_metadata.assert_valid_metadata();
+        objective.reward_curve.assert_valid_curve(objective.reward);
+        if let Some(vesting) = &objective.vesting {
+            vesting.assert_valid_schedule();
+        }
+        self.assert_valid_schedule(objective);
+
+        let obj_id = self.objectives.len() as ObjectiveId;
+        self.assert_valid_prerequisites(obj_id, objective);
+        self.index_quest_line(obj_id, None, objective.quest_line.as_ref());
+        self.assert_valid_labels(objective);
+        self.index_labels(obj_id, None, objective);
+        objective.assert_valid_location();
+        self.index_location(obj_id, None, objective.location.as_ref());
+
+        let mut obj_metadata_list = self.obj_metadata.get().unwrap();
+
+        self.objectives.push(objective);
+        obj_metadata_list.push(obj_metadata);
+        self.stats.push(stats);
+
+        self.obj_metadata.set(&obj_metadata_list);
+
+        obj_id
+    }
+
+    /// Edits title, reward and metadata of an existing Objective, None fields are left unchanged
+    pub fn edit_objective(
+        &mut self,
+        obj_id: ObjectiveId,
+        title: Option<String>,
+        reward: Option<Balance>,
+        obj_metadata: Option<ObjectiveMetadata>,
+    ) -> (Objective, ObjectiveMetadata) {
+        let mut objective = self.get_objective(obj_id);
+
+        if let Some(title) = title {
+            objective.title = title;
+        }
+        if let Some(reward) = reward {
+            objective.reward_curve.assert_valid_curve(reward);
+            objective.reward = reward;
+        }
+        self.objectives.replace(obj_id.into(), &objective);
+
+        let mut obj_metadata_list = self.obj_metadata.get().unwrap();
+
+        let obj_metadata = match obj_metadata {
+            Some(obj_metadata) => {
+                obj_metadata.assert_valid_metadata();
+                obj_metadata_list.replace(obj_id.into(), &obj_metadata);
+                self.obj_metadata.set(&obj_metadata_list);
+                obj_metadata
+            }
+            None => obj_metadata_list
+                .get(obj_id.into())
+                .unwrap_or_else(|| env::panic(b"Invariant : Invalid Objective")),
+        };
+
+        (objective, obj_metadata)
+    }
+
+    /// Marks the Objective as retired, so that it can't be rewarded anymore
+    pub fn retire_objective(&mut self, obj_id: ObjectiveId) {
+        let mut objective = self.get_objective(obj_id);
+
+        require!(!objective.retired, "Objective is already retired");
+
+        objective.retired = true;
+        self.objectives.replace(obj_id.into(), &objective);
+    }
+
+    /// Records the Objectives as rewarded to the player, panics if any of them was already rewarded
+    pub fn record_player_objectives(&mut self, player_id: &AccountId, obj_ids: &[ObjectiveId]) {
+        let mut rewarded = self.player_objectives.get(player_id).unwrap_or_else(|| {
+            UnorderedMap::new(
+                StorageKey::PlayerObjectiveList {
+                    account_hash: env::sha256(player_id.as_bytes()),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+
+        let rewarded_at = env::block_timestamp();
+
+        for obj_id in obj_ids.iter() {
+            require!(
+                rewarded.insert(obj_id, &rewarded_at).is_none(),
+                "Objective is already rewarded to the player"
+            );
         }
+
+        self.player_objectives.insert(player_id, &rewarded);
+    }
+
+    /// Removes the reward ledger and the nonce of the player
+    pub fn remove_player_records(&mut self, player_id: &AccountId) {
+        if let Some(mut rewarded) = self.player_objectives.remove(player_id) {
+            rewarded.clear();
+        }
+        self.reward_nonces.remove(player_id);
+    }
+
+    /// Consumes the reward nonce of the player, panics if it is not the expected one
+    pub fn use_reward_nonce(&mut self, player_id: &AccountId, nonce: u64) {
+        let expected_nonce = self.reward_nonces.get(player_id).unwrap_or(0);
+
+        require!(
+            nonce == expected_nonce,
+            format!("Invalid reward nonce, expected {}", expected_nonce)
+        );
+
+        self.reward_nonces.insert(player_id, &(expected_nonce + 1));
+    }
+
+    pub fn get_objective(&self, obj_id: ObjectiveId) -> Objective {
+        self.objectives
+            .get(obj_id.into())
+            .unwrap_or_else(|| env::panic(b"Invariant : Invalid Objective"))
     }
 
-    // It will increase the winner_count by 1 , and update rarity
-    pub fn update_stats(&mut self, index: u8) {
+    /// Returns None if the metadata of the Objective is missing, check_objectives_integrity() reports such Objectives
+    pub fn json_objective(
+        &self,
+        obj_id: ObjectiveId,
+        objective: Objective,
+        obj_stat: ObjectiveStats,
+        obj_status: ObjectiveStatus,
+        obj_metadata_list: &Vector<ObjectiveMetadata>,
+    ) -> Option<JsonObjective> {
+        let obj_metadata = obj_metadata_list.get(obj_id.into())?;
+        let current_reward = objective
+            .reward_curve
+            .reward_at(objective.reward, &obj_stat);
+
+        Some(JsonObjective::from((
+            obj_id,
+            objective,
+            obj_metadata,
+            obj_stat,
+            obj_status,
+            current_reward,
+        )))
+    }
+
+    // It will increase the winner_count by 1 , and update rarity, returns the new rarity
+    pub fn update_stats(&mut self, obj_id: ObjectiveId, registered_accounts: u64) -> Rarity {
         // It is safe to query stats with the index given, bcz it is only called by transfer_player_reward()
         let obj_stat = self
             .stats
-            .get(index.into())
+            .get(obj_id.into())
             .unwrap_or_else(|| env::panic(b"Invariant : Invalid Objective"));
 
         let new_winner_count = obj_stat.winner_count + 1;
 
-        let new_rarity = match new_winner_count {
-            0..=1000 => Rarity::Legendary,
-            1001..=20000 => Rarity::Rare,
-            _ => Rarity::Common,
-        };
+        let new_rarity = self
+            .get_rarity_thresholds(obj_id)
+            .rarity_of(new_winner_count, registered_accounts);
 
         let new_stat = ObjectiveStats {
             winner_count: new_winner_count,
             rarity: new_rarity,
         };
 
-        self.stats.replace(index.into(), &new_stat);
+        self.stats.replace(obj_id.into(), &new_stat);
+
+        new_rarity
     }
 }
 
@@ -138,10 +466,32 @@ impl Objective {
             Objective {
                 title: String::from("Objective1"),
                 reward: 1000,
+                retired: false,
+                starts_at: None,
+                ends_at: None,
+                season_id: None,
+                prerequisites: vec![],
+                quest_line: None,
+                reward_curve: RewardCurve::Flat,
+                vesting: None,
+                category: None,
+                tags: vec![],
+                location: None,
             },
             Objective {
                 title: String::from("Objective2"),
                 reward: 2000,
+                retired: false,
+                starts_at: None,
+                ends_at: None,
+                season_id: None,
+                prerequisites: vec![],
+                quest_line: None,
+                reward_curve: RewardCurve::Flat,
+                vesting: None,
+                category: None,
+                tags: vec![],
+                location: None,
             },
         ]
     }
@@ -160,6 +510,10 @@ impl ObjectiveMetadata {
             },
         ]
     }
+
+    pub fn assert_valid_metadata(&self) {
+        require!(self.reference_hash.0.len() == 32, "Hash has to be 32 bytes");
+    }
 }
 
 impl ObjectiveStats {
@@ -167,80 +521,529 @@ impl ObjectiveStats {
         vec![
             ObjectiveStats {
                 winner_count: 0,
-                rarity: Rarity::Common,
+                rarity: Rarity::Legendary,
             },
             ObjectiveStats {
                 winner_count: 0,
-                rarity: Rarity::Common,
+                rarity: Rarity::Legendary,
             },
         ]
     }
 }
 
-impl From<(Objective, ObjectiveMetadata, ObjectiveStats)> for JsonObjective {
-    fn from(item: (Objective, ObjectiveMetadata, ObjectiveStats)) -> Self {
+impl
+    From<(
+        ObjectiveId,
+        Objective,
+        ObjectiveMetadata,
+        ObjectiveStats,
+        ObjectiveStatus,
+        Balance,
+    )> for JsonObjective
+{
+    fn from(
+        item: (
+            ObjectiveId,
+            Objective,
+            ObjectiveMetadata,
+            ObjectiveStats,
+            ObjectiveStatus,
+            Balance,
+        ),
+    ) -> Self {
         Self {
-            objective: item.0,
-            obj_metadata: item.1,
-            stats: item.2,
+            id: item.0,
+            objective: item.1,
+            obj_metadata: item.2,
+            stats: item.3,
+            status: item.4,
+            current_reward: U128::from(item.5),
         }
     }
 }
 
-// ToDo
-
-// Ensure that for every ping on NFT contract some 5 to 7 (yet to calculate) index can only be sent because of the gas limit , we cannot perform unlimited operation
+// Only some 5 to 7 Objectives can be rewarded in one transfer_player_reward() call because of the gas limit,
+// transfer_batch_reward() should be used for rewarding many players at once
 
-// Ensure that NFT Contract callback function checks if transfer_player_reward() ran successfully then make state changes like registering those achievements as rewarded it should not be done before callback
+// NFT Contract callback should register the achievements as rewarded only after verifying the RewardReceipt returned by transfer_player_reward()
 
 #[near_bindgen]
 impl Contract {
     /// Transfer Fungible Token Rewards to players
-    pub fn transfer_player_reward(&mut self, player_id: AccountId, obj_index: Base64VecU8) {
-        require!(
-            env::predecessor_account_id().as_str() == "nft.catchlabs.near",
-            "Reward distribution can only be handled by CatchLabs NFT Contract"
-        );
+    ///
+    /// nonce has to be the current reward nonce of the player, so that replayed or out of order calls are rejected
+    ///
+    /// returns a RewardReceipt to be verified by the NFT Contract callback
+    pub fn transfer_player_reward(
+        &mut self,
+        player_id: AccountId,
+        obj_ids: Vec<ObjectiveId>,
+        nonce: U64,
+    ) -> RewardReceipt {
+        self.assert_role(Role::RewardDistributor);
+
+        self.internal_reward_player(player_id, obj_ids, nonce)
+    }
 
-        require!(
-            self.token.accounts.contains_key(&player_id),
-            "Player is Not Registered with the FT contract"
+    /// Transfer Fungible Token Rewards to many players in one call
+    ///
+    /// Requests are processed in order until the gas left is not enough for one more request,
+    /// next_index of the result tells from where the caller should resume
+    ///
+    /// Invalid requests are skipped and reported in failed instead of reverting the whole batch
+    pub fn transfer_batch_reward(
+        &mut self,
+        requests: Vec<PlayerRewardRequest>,
+    ) -> BatchRewardResult {
+        self.assert_role(Role::RewardDistributor);
+        self.assert_not_paused(PauseFlag::Rewards);
+
+        let gas_limit = env::prepaid_gas().saturating_sub(GAS_FOR_BATCH_RESULT);
+
+        let mut receipts = Vec::with_capacity(requests.len());
+        let mut failed = vec![];
+        let mut next_index = None;
+        let mut request_gas = GAS_FOR_REWARD_REQUEST;
+
+        for (index, request) in requests.into_iter().enumerate() {
+            let used_gas = env::used_gas();
+
+            if used_gas + request_gas > gas_limit {
+                next_index = Some(index as u32);
+                break;
+            }
+
+            // A panic would revert the requests already processed, so invalid requests are skipped instead
+            match self.internal_try_reward_player(request.player_id, request.obj_ids, request.nonce)
+            {
+                Ok(receipt) => receipts.push(receipt),
+                Err(reason) => {
+                    failed.push((index as u32, reason));
+                    continue;
+                }
+            }
+
+            // Most expensive request so far is used as the estimate for the next one
+            request_gas = std::cmp::max(request_gas, env::used_gas() - used_gas);
+        }
+
+        BatchRewardResult {
+            receipts,
+            next_index,
+            failed,
+        }
+    }
+
+    /// View Function - returns the nonce to be used for the next reward of the player
+    pub fn get_reward_nonce(&self, player_id: AccountId) -> U64 {
+        U64::from(
+            self.catch_objectives
+                .reward_nonces
+                .get(&player_id)
+                .unwrap_or(0),
+        )
+    }
+
+    /// MetadataManager Only - Add a new Objective along with its metadata and initial stats
+    ///
+    /// returns index of the newly added Objective
+    pub fn add_objective(
+        &mut self,
+        objective: Objective,
+        obj_metadata: ObjectiveMetadata,
+        stats: ObjectiveStats,
+    ) -> ObjectiveId {
+        self.assert_role(Role::MetadataManager);
+
+        let obj_id = self
+            .catch_objectives
+            .add_objective(&objective, &obj_metadata, &stats);
+
+        // Rarity is always derived from winner_count and the Rarity Thresholds
+        self.catch_objectives
+            .refresh_rarity(obj_id, self.token.registered_accounts);
+
+        ObjectiveAddLog {
+            obj_id,
+            title: objective.title,
+            reward: U128::from(objective.reward),
+        }
+        .emit();
+
+        obj_id
+    }
+
+    /// MetadataManager Only - Edit title, reward and metadata of an existing Objective
+    ///
+    /// Fields which are not passed are left unchanged
+    pub fn edit_objective(
+        &mut self,
+        obj_id: ObjectiveId,
+        title: Option<String>,
+        reward: Option<U128>,
+        obj_metadata: Option<ObjectiveMetadata>,
+    ) {
+        self.assert_role(Role::MetadataManager);
+
+        let (objective, obj_metadata) = self.catch_objectives.edit_objective(
+            obj_id,
+            title,
+            reward.map(|reward| reward.into()),
+            obj_metadata,
         );
 
-        let mut prize: Balance = 0;
-        let indexes = obj_index.0;
-        let mut objective;
+        ObjectiveUpdateLog {
+            obj_id,
+            title: objective.title,
+            reward: U128::from(objective.reward),
+            reference: obj_metadata.reference,
+        }
+        .emit();
+    }
+
+    /// MetadataManager Only - Retire an Objective, it can't be rewarded anymore but remains queryable
+    pub fn retire_objective(&mut self, obj_id: ObjectiveId) {
+        self.assert_role(Role::MetadataManager);
+
+        self.catch_objectives.retire_objective(obj_id);
+
+        ObjectiveRetireLog { obj_id }.emit();
+    }
+
+    /// View Function - returns the Objectives already rewarded to the player along with the time of reward
+    pub fn get_player_objectives(&self, player_id: AccountId) -> Vec<PlayerObjective> {
+        match self.catch_objectives.player_objectives.get(&player_id) {
+            Some(rewarded) => rewarded
+                .iter()
+                .map(|(obj_id, rewarded_at)| PlayerObjective {
+                    obj_id,
+                    rewarded_at: U64::from(rewarded_at),
+                })
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// View Function - returns the Objective Info, None if it doesn't exist
+    pub fn get_objective(&self, obj_id: ObjectiveId) -> Option<JsonObjective> {
+        let catch_objectives = &self.catch_objectives;
+
+        let objective = catch_objectives.objectives.get(obj_id.into())?;
+        let obj_stat = catch_objectives.stats.get(obj_id.into())?;
+        let obj_status = catch_objectives.objective_status(&objective);
+
+        catch_objectives.json_objective(
+            obj_id,
+            objective,
+            obj_stat,
+            obj_status,
+            &catch_objectives.obj_metadata.get()?,
+        )
+    }
+
+    /// View Function - returns the number of Objectives, retired ones included
+    pub fn get_objective_count(&self) -> u64 {
+        self.catch_objectives.objectives.len()
+    }
 
-        let owner_id = self.owner_id.clone();
+    /// View Function - returns paginated view of Objectives Info, starting from the Objective with id from_id
+    ///
+    /// If status is passed, only the Objectives with that status are returned, up to limit of them
+    ///
+    /// Objectives with missing metadata or stats are skipped, check_objectives_integrity() reports them
+    pub fn get_objectives(
+        &self,
+        from_id: ObjectiveId,
+        limit: u32,
+        status: Option<ObjectiveStatus>,
+    ) -> Vec<JsonObjective> {
+        let obj_metadata_list = match self.catch_objectives.obj_metadata.get() {
+            Some(obj_metadata_list) => obj_metadata_list,
+            None => return vec![],
+        };
 
-        for i in indexes.into_iter() {
-            objective = self
+        (u64::from(from_id)..self.catch_objectives.objectives.len())
+            .filter_map(|i| {
+                let objective = self.catch_objectives.objectives.get(i).unwrap();
+                let obj_status = self.catch_objectives.objective_status(&objective);
+
+                if status.is_some_and(|status| status != obj_status) {
+                    return None;
+                }
+
+                // Metadata and stats are read only for the Objectives being returned
+                self.catch_objectives.json_objective(
+                    i as ObjectiveId,
+                    objective,
+                    self.catch_objectives.stats.get(i)?,
+                    obj_status,
+                    &obj_metadata_list,
+                )
+            })
+            .take(limit as usize)
+            .collect()
+    }
+}
+
+/*****************************************/
+/*  INTERNAL FUNCTIONS - PLAYER REWARDS  */
+/*****************************************/
+
+impl Contract {
+    /// Validates and pays the reward of the player, panics with the reason if it is invalid
+    pub fn internal_reward_player(
+        &mut self,
+        player_id: AccountId,
+        obj_ids: Vec<ObjectiveId>,
+        nonce: U64,
+    ) -> RewardReceipt {
+        self.internal_try_reward_player(player_id, obj_ids, nonce)
+            .unwrap_or_else(|reason| env::panic(reason.as_bytes()))
+    }
+
+    /// Validates and pays the reward of the player, nothing is changed if it is invalid
+    ///
+    /// returns the reason the reward is invalid
+    pub fn internal_try_reward_player(
+        &mut self,
+        player_id: AccountId,
+        obj_ids: Vec<ObjectiveId>,
+        nonce: U64,
+    ) -> Result<RewardReceipt, String> {
+        let plan = self.internal_plan_reward(&player_id, &obj_ids, Some(nonce.into()), None)?;
+        let prize = self.internal_apply_reward(&player_id, &obj_ids, plan);
+
+        Ok(RewardReceipt {
+            player_id,
+            obj_ids,
+            amount: U128::from(prize),
+            nonce,
+        })
+    }
+
+    /// Pays the Objectives to the player, returns the total paid
+    ///
+    /// fixed_rewards are paid instead of the Reward Curves if passed, in the same order as obj_ids
+    pub fn internal_pay_objectives(
+        &mut self,
+        player_id: &AccountId,
+        obj_ids: &[ObjectiveId],
+        fixed_rewards: Option<&[Balance]>,
+    ) -> Balance {
+        let plan = self
+            .internal_plan_reward(player_id, obj_ids, None, fixed_rewards)
+            .unwrap_or_else(|reason| env::panic(reason.as_bytes()));
+
+        self.internal_apply_reward(player_id, obj_ids, plan)
+    }
+
+    /// Checks the reward of the Objectives to the player and computes what is paid, without changing any state
+    ///
+    /// nonce is checked against the reward nonce of the player if passed, an exhausted budget is also logged as an event
+    ///
+    /// returns the reason the reward is invalid
+    pub fn internal_plan_reward(
+        &self,
+        player_id: &AccountId,
+        obj_ids: &[ObjectiveId],
+        nonce: Option<u64>,
+        fixed_rewards: Option<&[Balance]>,
+    ) -> Result<RewardPlan, String> {
+        if !self.token.accounts.contains_key(player_id) {
+            return Err("Player is Not Registered with the FT contract".to_string());
+        }
+
+        if let Some(nonce) = nonce {
+            let expected_nonce = self
                 .catch_objectives
-                .objectives
-                .get(i.into())
-                .unwrap_or_else(|| env::panic(b"Invariant : Invalid Objective"));
+                .reward_nonces
+                .get(player_id)
+                .unwrap_or(0);
+            if nonce != expected_nonce {
+                return Err(format!("Invalid reward nonce, expected {}", expected_nonce));
+            }
+        }
+
+        if self.pause_state.is_paused(PauseFlag::Rewards) {
+            return Err(format!("{:?} are paused", PauseFlag::Rewards));
+        }
+        if !self.kyc.is_verified(player_id) {
+            return Err(format!("Account {} is not KYC verified", player_id));
+        }
+        if self.token.is_frozen_incoming(player_id) {
+            return Err(format!(
+                "Account {} is frozen for incoming transfers",
+                player_id
+            ));
+        }
+
+        let mut unique_obj_ids = obj_ids.to_vec();
+        unique_obj_ids.sort_unstable();
+        unique_obj_ids.dedup();
+        if unique_obj_ids.len() != obj_ids.len() {
+            return Err("Duplicate Objective in the reward request".to_string());
+        }
 
-            prize += objective.reward;
-            self.catch_objectives.update_stats(i);
+        let rewarded = self.catch_objectives.player_objectives.get(player_id);
+        let is_rewarded = |obj_id: &ObjectiveId| {
+            rewarded
+                .as_ref()
+                .is_some_and(|rewarded| rewarded.get(obj_id).is_some())
+        };
+
+        let mut rewards = Vec::with_capacity(obj_ids.len());
+        let mut from_pool: Balance = 0;
+
+        for (index, i) in obj_ids.iter().copied().enumerate() {
+            let objective = match self.catch_objectives.objectives.get(i.into()) {
+                Some(objective) => objective,
+                None => return Err("Invariant : Invalid Objective".to_string()),
+            };
+
+            if objective.retired {
+                return Err("Objective is retired".to_string());
+            }
+            match self.catch_objectives.objective_status(&objective) {
+                ObjectiveStatus::Upcoming => return Err("Objective hasn't started yet".to_string()),
+                ObjectiveStatus::Expired => return Err("Objective has ended".to_string()),
+                ObjectiveStatus::Active => {}
+            }
+
+            if is_rewarded(&i) {
+                return Err("Objective is already rewarded to the player".to_string());
+            }
+            // Prerequisites can be rewarded along with the Objective in the same request
+            if !objective
+                .prerequisites
+                .iter()
+                .all(|prerequisite| is_rewarded(prerequisite) || obj_ids.contains(prerequisite))
+            {
+                return Err(
+                    "Prerequisites of the Objective are not rewarded to the player".to_string(),
+                );
+            }
+
+            // Reward Curve is evaluated with the stats before this reward
+            let reward = match fixed_rewards {
+                Some(fixed_rewards) => fixed_rewards[index],
+                None => self.catch_objectives.current_reward(i, &objective),
+            };
+
+            self.treasury
+                .check_objective_budget(i, reward)
+                .map_err(RewardBudgetExhaustedLog::emit_and_reason)?;
+
+            // Sponsored Objectives are paid only from their escrow and never from the reward pool
+            if self.sponsorships.is_sponsored(i) {
+                self.sponsorships
+                    .check_spend(i, reward)
+                    .map_err(RewardBudgetExhaustedLog::emit_and_reason)?;
+            } else {
+                from_pool += reward;
+            }
+
+            rewards.push((objective, reward));
+        }
+
+        if !self.token.can_use_storage(
+            player_id,
+            self.reward_storage_usage * obj_ids.len() as StorageUsage,
+        ) {
+            return Err(
+                "Not enough storage deposit for the reward records, top it up with storage_deposit"
+                    .to_string(),
+            );
+        }
+
+        let schedules: Vec<&VestingSchedule> = rewards
+            .iter()
+            .filter_map(|(objective, _)| objective.vesting.as_ref())
+            .collect();
+        if !self.token.can_lock(player_id, &schedules) {
+            return Err("Account has too many vesting grants".to_string());
         }
 
-        self.token.internal_withdraw(&owner_id, prize);
-        self.token.internal_deposit(&player_id, prize);
+        self.treasury
+            .check_spend(from_pool)
+            .map_err(RewardBudgetExhaustedLog::emit_and_reason)?;
 
-        // ToDo - Transfer Reward Event
+        Ok(RewardPlan {
+            nonce,
+            rewards,
+            from_pool,
+        })
     }
 
-    /// View Function - returns paginated view of Objectives Info
-    pub fn get_objectives(&self, from_index: u8, limit: u8) -> Vec<JsonObjective> {
+    /// Pays a reward checked by internal_plan_reward(), returns the total paid
+    pub fn internal_apply_reward(
+        &mut self,
+        player_id: &AccountId,
+        obj_ids: &[ObjectiveId],
+        plan: RewardPlan,
+    ) -> Balance {
+        if let Some(nonce) = plan.nonce {
+            self.catch_objectives.use_reward_nonce(player_id, nonce);
+        }
+
         self.catch_objectives
-            .objectives
-            .iter()
-            .zip(self.catch_objectives.obj_metadata.get().unwrap().iter())
-            .zip(self.catch_objectives.stats.iter())
-            .skip(from_index.into())
-            .take(limit.into())
-            .map(|((obj, obj_meta), stat)| JsonObjective::from((obj, obj_meta, stat)))
-            .collect()
+            .record_player_objectives(player_id, obj_ids);
+        self.token.internal_use_storage(
+            player_id,
+            self.reward_storage_usage * obj_ids.len() as StorageUsage,
+        );
+
+        let mut prize: Balance = 0;
+        let mut locked: Balance = 0;
+        let mut rewards = Vec::with_capacity(obj_ids.len());
+        let mut rarities = Vec::with_capacity(obj_ids.len());
+
+        for (i, (objective, reward)) in obj_ids.iter().copied().zip(plan.rewards) {
+            self.treasury.spend_objective_budget(i, reward);
+
+            if self.sponsorships.is_sponsored(i) {
+                self.sponsorships.spend(i, reward);
+            }
+
+            prize += reward;
+            rewards.push(U128::from(reward));
+
+            if let Some(vesting) = &objective.vesting {
+                self.token.internal_lock(player_id, reward, vesting);
+                locked += reward;
+            }
+            let rarity = self
+                .catch_objectives
+                .update_stats(i, self.token.registered_accounts);
+            rarities.push(rarity);
+
+            self.leaderboard
+                .record_reward(player_id, reward, rarity, objective.season_id.as_ref());
+        }
+
+        self.treasury.spend(plan.from_pool);
+
+        // Locked rewards stay with the contract account until they are claimed
+        let unlocked = prize - locked;
+        if unlocked > 0 {
+            self.token.internal_transfer(
+                &env::current_account_id(),
+                player_id,
+                unlocked,
+                Some("reward".to_string()),
+            );
+        }
+
+        PlayerRewardLog {
+            player_id: player_id.clone(),
+            obj_ids: obj_ids.to_vec(),
+            rewards,
+            rarities,
+            total: U128::from(prize),
+            locked: U128::from(locked),
+        }
+        .emit();
+
+        prize
     }
 }
 
@@ -252,21 +1055,19 @@ mod game_obj_tests {
 
     use near_sdk::json_types::Base64VecU8;
     use near_sdk::testing_env;
-    use near_sdk::Balance;
     use near_sdk::MockedBlockchain;
-
-    const STORAGE_COST: Balance = 1_250_000_000_000_000_000_000; // 1 Near = 10^24 Yocto Near
+    use near_sdk::{RuntimeFeesConfig, VMConfig};
 
     #[test]
-    #[should_panic(expected = "Reward distribution can only be handled by CatchLabs NFT Contract")]
+    #[should_panic(expected = "Only accounts with RewardDistributor role can call this method")]
     fn transfer_reward_invalid_caller() {
         testing_env!(get_context(dex().to_string(), 0));
 
         let mut contract = create_contract();
         let player = carol().to_string();
-        let indexes = Base64VecU8::from([0].to_vec());
+        let obj_ids = vec![0];
 
-        contract.transfer_player_reward(player, indexes);
+        contract.transfer_player_reward(player, obj_ids, U64::from(0));
     }
 
     #[test]
@@ -276,9 +1077,9 @@ mod game_obj_tests {
 
         let mut contract = create_contract();
         let player = carol().to_string();
-        let indexes = Base64VecU8::from([0].to_vec());
+        let obj_ids = vec![0];
 
-        contract.transfer_player_reward(player, indexes);
+        contract.transfer_player_reward(player, obj_ids, U64::from(0));
     }
 
     #[test]
@@ -288,13 +1089,14 @@ mod game_obj_tests {
 
         let mut contract = create_contract();
         contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
 
         testing_env!(get_context(nft().to_string(), 0));
 
         let player = carol().to_string();
-        let indexes = Base64VecU8::from([2].to_vec());
+        let obj_ids = vec![2];
 
-        contract.transfer_player_reward(player, indexes);
+        contract.transfer_player_reward(player, obj_ids, U64::from(0));
     }
 
     #[test]
@@ -303,31 +1105,442 @@ mod game_obj_tests {
 
         let mut contract = create_contract();
         contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
 
         testing_env!(get_context(nft().to_string(), 0));
 
         let player = carol().to_string();
-        let indexes = Base64VecU8::from([0, 1].to_vec());
+        let obj_ids = vec![0, 1];
 
         let rewards = 3000;
 
-        contract.transfer_player_reward(player, indexes);
+        contract.transfer_player_reward(player, obj_ids, U64::from(0));
         assert_eq!(contract.ft_balance_of(carol()).0, rewards);
     }
 
+    #[test]
+    fn transfer_reward_events() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let player = carol().to_string();
+        let obj_ids = vec![1, 0];
+
+        contract.transfer_player_reward(player, obj_ids, U64::from(0));
+
+        assert_eq!(
+            near_sdk::test_utils::get_logs(),
+            vec![
+                r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":{"old_owner_id":"mike.near","new_owner_id":"carol.near","amount":"3000","memo":"reward"}}"#,
+                r#"EVENT_JSON:{"standard":"catch","version":"1.0.0","event":"player_reward","data":{"player_id":"carol.near","obj_ids":[1,0],"rewards":["2000","1000"],"rarities":["Legendary","Legendary"],"total":"3000","locked":"0"}}"#,
+            ]
+        );
+    }
+
+    // Sets the result of transfer_player_reward() as seen by the NFT Contract callback
+    fn set_reward_result(result: PromiseResult) {
+        testing_env!(
+            get_context(nft().to_string(), 0),
+            VMConfig::default(),
+            RuntimeFeesConfig::default(),
+            Default::default(),
+            vec![result]
+        );
+    }
+
+    #[test]
+    fn reward_callback_success() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        let mut mock_nft = MockNftContract::default();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let player = carol().to_string();
+        let nonce = mock_nft.next_nonce(&player);
+
+        let receipt = contract.transfer_player_reward(player.clone(), vec![0, 1], nonce);
+        assert_eq!(
+            receipt,
+            RewardReceipt {
+                player_id: player.clone(),
+                obj_ids: vec![0, 1],
+                amount: U128::from(3000),
+                nonce: U64::from(0),
+            }
+        );
+
+        set_reward_result(PromiseResult::Successful(
+            near_sdk::serde_json::to_vec(&receipt).unwrap(),
+        ));
+        assert!(mock_nft.on_reward_transferred(player.clone(), vec![0, 1], nonce));
+
+        assert_eq!(mock_nft.rewarded[&player], vec![0, 1]);
+        assert_eq!(
+            mock_nft.next_nonce(&player),
+            contract.get_reward_nonce(player)
+        );
+    }
+
+    #[test]
+    fn reward_callback_failure() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        let mut mock_nft = MockNftContract::default();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+
+        let player = carol().to_string();
+        let nonce = mock_nft.next_nonce(&player);
+
+        // Reward pool is empty, so transfer_player_reward() fails and the achievements stay unrewarded
+        set_reward_result(PromiseResult::Failed);
+        assert!(!mock_nft.on_reward_transferred(player.clone(), vec![0], nonce));
+        assert!(!mock_nft.rewarded.contains_key(&player));
+
+        // Retry with the same nonce once the pool is funded
+        seed_reward_pool(&mut contract, 5000);
+        testing_env!(get_context(nft().to_string(), 0));
+        let receipt = contract.transfer_player_reward(player.clone(), vec![0], nonce);
+
+        // A receipt not matching the request is rejected as well
+        set_reward_result(PromiseResult::Successful(
+            near_sdk::serde_json::to_vec(&receipt).unwrap(),
+        ));
+        assert!(!mock_nft.on_reward_transferred(player.clone(), vec![1], nonce));
+        assert!(mock_nft.on_reward_transferred(player.clone(), vec![0], nonce));
+        assert_eq!(mock_nft.rewarded[&player], vec![0]);
+    }
+
+    #[test]
+    fn reward_callback_after_failed_call() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        let mut mock_nft = MockNftContract::default();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        testing_env!(get_context(nft().to_string(), 0));
+
+        // Two requests are sent with the same nonce before either callback has run
+        let player = carol().to_string();
+        let nonce = mock_nft.next_nonce(&player);
+
+        let receipt = contract.transfer_player_reward(player.clone(), vec![0], nonce);
+        let failed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.transfer_player_reward(player.clone(), vec![1], nonce)
+        }));
+        assert!(failed
+            .unwrap_err()
+            .downcast_ref::<String>()
+            .unwrap()
+            .contains("Invalid reward nonce, expected 1"));
+
+        set_reward_result(PromiseResult::Successful(
+            near_sdk::serde_json::to_vec(&receipt).unwrap(),
+        ));
+        assert!(mock_nft.on_reward_transferred(player.clone(), vec![0], nonce));
+
+        // Failed call neither advances the nonce nor marks its achievement as rewarded
+        set_reward_result(PromiseResult::Failed);
+        assert!(!mock_nft.on_reward_transferred(player.clone(), vec![1], nonce));
+        assert_eq!(mock_nft.rewarded[&player], vec![0]);
+        assert_eq!(mock_nft.next_nonce(&player), U64::from(1));
+        assert_eq!(contract.get_reward_nonce(player.clone()), U64::from(1));
+        assert_eq!(contract.ft_balance_of(carol()).0, 1000);
+
+        // Retry with the next nonce goes through
+        let nonce = mock_nft.next_nonce(&player);
+        let receipt = contract.transfer_player_reward(player.clone(), vec![1], nonce);
+
+        set_reward_result(PromiseResult::Successful(
+            near_sdk::serde_json::to_vec(&receipt).unwrap(),
+        ));
+        assert!(mock_nft.on_reward_transferred(player.clone(), vec![1], nonce));
+        assert_eq!(mock_nft.rewarded[&player], vec![0, 1]);
+        assert_eq!(contract.ft_balance_of(carol()).0, 3000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid reward nonce, expected 1")]
+    fn transfer_reward_replayed_nonce() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let player = carol().to_string();
+
+        contract.transfer_player_reward(player.clone(), vec![0], U64::from(0));
+        contract.transfer_player_reward(player, vec![1], U64::from(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid reward nonce, expected 0")]
+    fn transfer_reward_out_of_order_nonce() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        testing_env!(get_context(nft().to_string(), 0));
+
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(1));
+    }
+
+    fn batch_requests(
+        contract: &mut Contract,
+        players: &[ValidAccountId],
+    ) -> Vec<PlayerRewardRequest> {
+        verify_players(contract, players);
+
+        players
+            .iter()
+            .map(|player| {
+                contract.storage_deposit(Some(player.clone()));
+                PlayerRewardRequest {
+                    player_id: player.to_string(),
+                    obj_ids: vec![0, 1],
+                    nonce: U64::from(0),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn transfer_batch_reward() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        let requests = batch_requests(&mut contract, &[alice(), bob(), carol()]);
+        seed_reward_pool(&mut contract, 10000);
+
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let result = contract.transfer_batch_reward(requests);
+
+        assert_eq!(result.next_index, None);
+        assert_eq!(result.receipts.len(), 3);
+        assert_eq!(result.receipts[2].player_id, carol().to_string());
+        assert_eq!(contract.ft_balance_of(alice()).0, 3000);
+        assert_eq!(contract.ft_balance_of(carol()).0, 3000);
+    }
+
+    #[test]
+    fn transfer_batch_reward_gas_limit() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        let requests = batch_requests(&mut contract, &[alice(), bob(), carol()]);
+        seed_reward_pool(&mut contract, 10000);
+
+        // Enough gas for only a single request
+        let mut context = get_context(nft().to_string(), 0);
+        context.prepaid_gas = GAS_FOR_BATCH_RESULT + GAS_FOR_REWARD_REQUEST + 1_000_000_000_000;
+        testing_env!(context);
+
+        let mut requests = requests;
+        let result = contract.transfer_batch_reward(requests.clone());
+
+        assert_eq!(result.next_index, Some(1));
+        assert_eq!(result.receipts.len(), 1);
+        assert_eq!(contract.ft_balance_of(alice()).0, 3000);
+        assert_eq!(contract.ft_balance_of(bob()).0, 0);
+
+        // Resume from the remainder
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let result = contract.transfer_batch_reward(requests.split_off(1));
+
+        assert_eq!(result.next_index, None);
+        assert_eq!(result.receipts.len(), 2);
+        assert_eq!(contract.ft_balance_of(bob()).0, 3000);
+        assert_eq!(contract.ft_balance_of(carol()).0, 3000);
+    }
+
+    #[test]
+    fn transfer_batch_reward_skips_invalid() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        let mut requests = batch_requests(&mut contract, &[alice(), bob(), carol()]);
+        seed_reward_pool(&mut contract, 10000);
+
+        let dave = ValidAccountId::try_from("dave.near").unwrap();
+        requests.insert(
+            1,
+            PlayerRewardRequest {
+                player_id: dave.to_string(),
+                obj_ids: vec![0],
+                nonce: U64::from(0),
+            },
+        );
+        requests[2].obj_ids = vec![1, 1];
+        requests.push(PlayerRewardRequest {
+            player_id: alice().to_string(),
+            obj_ids: vec![0],
+            nonce: U64::from(0),
+        });
+        requests.push(PlayerRewardRequest {
+            player_id: alice().to_string(),
+            obj_ids: vec![0],
+            nonce: U64::from(1),
+        });
+
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let result = contract.transfer_batch_reward(requests);
+
+        assert_eq!(result.next_index, None);
+        assert_eq!(
+            result.failed,
+            vec![
+                (
+                    1,
+                    "Player is Not Registered with the FT contract".to_string()
+                ),
+                (2, "Duplicate Objective in the reward request".to_string()),
+                (4, "Invalid reward nonce, expected 1".to_string()),
+                (5, "Objective is already rewarded to the player".to_string()),
+            ]
+        );
+        assert_eq!(result.receipts.len(), 2);
+        assert_eq!(result.receipts[1].player_id, carol().to_string());
+        assert_eq!(contract.ft_balance_of(alice()).0, 3000);
+        assert_eq!(contract.ft_balance_of(bob()).0, 0);
+        assert_eq!(contract.ft_balance_of(carol()).0, 3000);
+        assert_eq!(contract.get_reward_nonce(bob().to_string()), U64::from(0));
+    }
+
+    #[test]
+    fn transfer_batch_reward_pool_exhausted() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        let requests = batch_requests(&mut contract, &[alice(), bob(), carol()]);
+        seed_reward_pool(&mut contract, 6000);
+
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let result = contract.transfer_batch_reward(requests);
+
+        assert_eq!(
+            result.failed,
+            vec![(2, "Reward pool doesn't have enough balance".to_string())]
+        );
+        assert!(near_sdk::test_utils::get_logs().contains(
+            &r#"EVENT_JSON:{"standard":"catch","version":"1.0.0","event":"reward_budget_exhausted","data":{"budget":"pool","requested":"3000","remaining":"0"}}"#.to_string()
+        ));
+        assert_eq!(contract.ft_balance_of(bob()).0, 3000);
+        assert_eq!(contract.ft_balance_of(carol()).0, 0);
+        assert_eq!(contract.get_reward_pool().balance.0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate Objective in the reward request")]
+    fn transfer_reward_duplicate_in_batch() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let player = carol().to_string();
+        let obj_ids = vec![1, 0, 1];
+
+        contract.transfer_player_reward(player, obj_ids, U64::from(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Objective is already rewarded to the player")]
+    fn transfer_reward_twice() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let player = carol().to_string();
+
+        contract.transfer_player_reward(player.clone(), vec![0], U64::from(0));
+        contract.transfer_player_reward(player, vec![1, 0], U64::from(1));
+    }
+
+    #[test]
+    fn get_player_objectives() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        let player = carol().to_string();
+        assert!(contract.get_player_objectives(player.clone()).is_empty());
+
+        at(nft(), 0, 42);
+
+        contract.transfer_player_reward(player.clone(), vec![1], U64::from(0));
+
+        assert_eq!(
+            contract.get_player_objectives(player),
+            vec![PlayerObjective {
+                obj_id: 1,
+                rewarded_at: U64::from(42),
+            }]
+        );
+    }
+
     #[test]
     fn get_objectives() {
         testing_env!(get_context(dex().to_string(), 0));
 
         let contract = create_contract();
 
-        let json_objs = contract.get_objectives(2, 3);
+        let json_objs = contract.get_objectives(0, 3, None);
 
         let expected = vec![
             JsonObjective {
+                id: 0,
                 objective: Objective {
                     title: String::from("Objective1"),
                     reward: 1000,
+                    retired: false,
+                    starts_at: None,
+                    ends_at: None,
+                    season_id: None,
+                    prerequisites: vec![],
+                    quest_line: None,
+                    reward_curve: RewardCurve::Flat,
+                    vesting: None,
+                    category: None,
+                    tags: vec![],
+                    location: None,
                 },
                 obj_metadata: ObjectiveMetadata {
                     reference: "ipfs://achievement1".to_string(),
@@ -335,13 +1548,27 @@ mod game_obj_tests {
                 },
                 stats: ObjectiveStats {
                     winner_count: 0,
-                    rarity: Rarity::Common,
+                    rarity: Rarity::Legendary,
                 },
+                status: ObjectiveStatus::Active,
+                current_reward: U128::from(1000),
             },
             JsonObjective {
+                id: 1,
                 objective: Objective {
                     title: String::from("Objective2"),
                     reward: 2000,
+                    retired: false,
+                    starts_at: None,
+                    ends_at: None,
+                    season_id: None,
+                    prerequisites: vec![],
+                    quest_line: None,
+                    reward_curve: RewardCurve::Flat,
+                    vesting: None,
+                    category: None,
+                    tags: vec![],
+                    location: None,
                 },
                 obj_metadata: ObjectiveMetadata {
                     reference: "ipfs://achievement2".to_string(),
@@ -349,12 +1576,130 @@ mod game_obj_tests {
                 },
                 stats: ObjectiveStats {
                     winner_count: 0,
-                    rarity: Rarity::Common,
+                    rarity: Rarity::Legendary,
                 },
+                status: ObjectiveStatus::Active,
+                current_reward: U128::from(2000),
             },
         ];
 
-        todo!()
-        // ToDo assert expected and returned value are same
+        assert_eq!(json_objs, expected);
+        assert!(contract.get_objectives(2, 3, None).is_empty());
+    }
+
+    #[test]
+    fn get_objective() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let contract = create_contract();
+
+        assert_eq!(contract.get_objective_count(), 2);
+        assert_eq!(
+            contract.get_objective(1),
+            contract.get_objectives(1, 1, None).pop()
+        );
+        assert!(contract.get_objective(2).is_none());
+    }
+
+    fn new_objective() -> (Objective, ObjectiveMetadata, ObjectiveStats) {
+        (
+            Objective {
+                title: String::from("Objective3"),
+                reward: 3000,
+                retired: false,
+                starts_at: None,
+                ends_at: None,
+                season_id: None,
+                prerequisites: vec![],
+                quest_line: None,
+                reward_curve: RewardCurve::Flat,
+                vesting: None,
+                category: None,
+                tags: vec![],
+                location: None,
+            },
+            ObjectiveMetadata {
+                reference: "ipfs://achievement3".to_string(),
+                reference_hash: Base64VecU8::from([3_u8; 32].to_vec()),
+            },
+            ObjectiveStats {
+                winner_count: 0,
+                rarity: Rarity::Legendary,
+            },
+        )
+    }
+
+    #[test]
+    fn add_objective() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        let (objective, obj_metadata, stats) = new_objective();
+
+        let obj_id = contract.add_objective(objective, obj_metadata, stats);
+        assert_eq!(obj_id, 2);
+
+        let json_objs = contract.get_objectives(2, 1, None);
+        assert_eq!(json_objs.len(), 1);
+        assert_eq!(json_objs[0].objective.title, "Objective3");
+        assert_eq!(json_objs[0].obj_metadata.reference, "ipfs://achievement3");
+        assert_eq!(json_objs[0].stats.rarity, Rarity::Legendary);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only accounts with MetadataManager role can call this method")]
+    fn add_objective_not_owner() {
+        testing_env!(get_context(carol().to_string(), 0));
+
+        let mut contract = create_contract();
+        let (objective, obj_metadata, stats) = new_objective();
+
+        contract.add_objective(objective, obj_metadata, stats);
+    }
+
+    #[test]
+    #[should_panic(expected = "Hash has to be 32 bytes")]
+    fn add_objective_invalid_metadata() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        let (objective, mut obj_metadata, stats) = new_objective();
+        obj_metadata.reference_hash = Base64VecU8::from([3_u8; 5].to_vec());
+
+        contract.add_objective(objective, obj_metadata, stats);
+    }
+
+    #[test]
+    fn edit_objective() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.edit_objective(1, None, Some(U128::from(2500)), None);
+
+        let json_objs = contract.get_objectives(1, 1, None);
+        assert_eq!(json_objs[0].objective.title, "Objective2");
+        assert_eq!(json_objs[0].objective.reward, 2500);
+        assert_eq!(json_objs[0].obj_metadata.reference, "ipfs://achievement2");
+    }
+
+    #[test]
+    #[should_panic(expected = "Objective is retired")]
+    fn transfer_reward_retired_objective() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        contract.retire_objective(1);
+
+        // Retired Objectives remain queryable
+        assert!(contract.get_objectives(1, 1, None)[0].objective.retired);
+
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let player = carol().to_string();
+        let obj_ids = vec![0, 1];
+
+        contract.transfer_player_reward(player, obj_ids, U64::from(0));
     }
 }
@@ -0,0 +1,368 @@
+use crate::*;
+
+use crate::catch_game::{JsonObjective, Objective};
+
+/// Max number of tags an Objective can have
+const MAX_TAGS: usize = 8;
+
+/// Max length in bytes of a category or a tag
+const MAX_LABEL_LENGTH: usize = 32;
+
+/// Filters of get_objectives_by(), None fields match every Objective
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectiveFilter {
+    pub category: Option<String>,
+    pub tag: Option<String>,
+    pub rarity: Option<Rarity>,
+    pub status: Option<ObjectiveStatus>,
+}
+
+fn assert_valid_label(label: &str) {
+    require!(
+        !label.is_empty() && label.len() <= MAX_LABEL_LENGTH,
+        format!(
+            "Categories and tags should be 1 to {} bytes long",
+            MAX_LABEL_LENGTH
+        )
+    );
+}
+
+/// Index of Objectives by label, categories and tags both use it
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LabelIndex {
+    /// Label -> Objectives having the label
+    pub objectives: UnorderedMap<String, UnorderedSet<ObjectiveId>>,
+
+    /// Used to derive the storage prefix of every label's set
+    pub prefix: Vec<u8>,
+}
+
+impl LabelIndex {
+    pub fn new(prefix: StorageKey) -> Self {
+        let prefix = prefix.try_to_vec().unwrap();
+
+        Self {
+            objectives: UnorderedMap::new(prefix.clone()),
+            prefix,
+        }
+    }
+
+    pub fn insert(&mut self, label: &String, obj_id: ObjectiveId) {
+        let mut obj_ids = self.objectives.get(label).unwrap_or_else(|| {
+            let mut set_prefix = self.prefix.clone();
+            set_prefix.extend(env::sha256(label.as_bytes()));
+
+            UnorderedSet::new(set_prefix)
+        });
+
+        obj_ids.insert(&obj_id);
+        self.objectives.insert(label, &obj_ids);
+    }
+
+    /// Objectives having the label, limit of them starting at from_index in the index order
+    pub fn page(&self, label: &String, from_index: u64, limit: u64) -> Vec<ObjectiveId> {
+        let obj_ids = match self.objectives.get(label) {
+            Some(obj_ids) => obj_ids,
+            None => return vec![],
+        };
+        let obj_ids = obj_ids.as_vector();
+
+        (from_index..std::cmp::min(from_index.saturating_add(limit), obj_ids.len()))
+            .filter_map(|index| obj_ids.get(index))
+            .collect()
+    }
+
+    pub fn remove(&mut self, label: &String, obj_id: ObjectiveId) {
+        if let Some(mut obj_ids) = self.objectives.get(label) {
+            obj_ids.remove(&obj_id);
+
+            if obj_ids.is_empty() {
+                self.objectives.remove(label);
+            } else {
+                self.objectives.insert(label, &obj_ids);
+            }
+        }
+    }
+}
+
+impl CatchObjectives {
+    pub fn assert_valid_labels(&self, objective: &Objective) {
+        if let Some(category) = &objective.category {
+            assert_valid_label(category);
+        }
+
+        require!(
+            objective.tags.len() <= MAX_TAGS,
+            format!("An Objective can have at most {} tags", MAX_TAGS)
+        );
+
+        for (i, tag) in objective.tags.iter().enumerate() {
+            assert_valid_label(tag);
+            require!(!objective.tags[..i].contains(tag), "Duplicate tag");
+        }
+    }
+
+    /// Moves the Objective from the category and tags of old_objective to the ones of new_objective
+    pub fn index_labels(
+        &mut self,
+        obj_id: ObjectiveId,
+        old_objective: Option<&Objective>,
+        new_objective: &Objective,
+    ) {
+        if let Some(old_objective) = old_objective {
+            if let Some(category) = &old_objective.category {
+                self.categories.remove(category, obj_id);
+            }
+            for tag in old_objective.tags.iter() {
+                self.tags.remove(tag, obj_id);
+            }
+        }
+
+        if let Some(category) = &new_objective.category {
+            self.categories.insert(category, obj_id);
+        }
+        for tag in new_objective.tags.iter() {
+            self.tags.insert(tag, obj_id);
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// MetadataManager Only - Set the category and tags of an Objective, None removes the category
+    pub fn set_objective_labels(
+        &mut self,
+        obj_id: ObjectiveId,
+        category: Option<String>,
+        tags: Vec<String>,
+    ) {
+        self.assert_role(Role::MetadataManager);
+
+        let old_objective = self.catch_objectives.get_objective(obj_id);
+
+        let mut objective = self.catch_objectives.get_objective(obj_id);
+        objective.category = category;
+        objective.tags = tags;
+
+        self.catch_objectives.assert_valid_labels(&objective);
+        self.catch_objectives
+            .index_labels(obj_id, Some(&old_objective), &objective);
+        self.catch_objectives
+            .objectives
+            .replace(obj_id.into(), &objective);
+
+        ObjectiveLabelsUpdateLog {
+            obj_id,
+            category: objective.category,
+            tags: objective.tags,
+        }
+        .emit();
+    }
+
+    /// View Function - returns the Objectives matching every filter within a page of the category, or the tag
+    ///
+    /// from_index and limit page over the Objectives of the category, of the tag if there is no category filter
+    /// or over every Objective, so a page can be shorter than limit when the other filters don't match
+    pub fn get_objectives_by(
+        &self,
+        filter: ObjectiveFilter,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<JsonObjective> {
+        let catch_objectives = &self.catch_objectives;
+
+        // Narrowest index is paged over, remaining filters are checked on each of its Objectives
+        let candidates: Vec<ObjectiveId> = match (&filter.category, &filter.tag) {
+            (Some(category), _) => catch_objectives
+                .categories
+                .page(category, from_index, limit),
+            (None, Some(tag)) => catch_objectives.tags.page(tag, from_index, limit),
+            (None, None) => (from_index
+                ..std::cmp::min(
+                    from_index.saturating_add(limit),
+                    catch_objectives.objectives.len(),
+                ))
+                .map(|obj_id| obj_id as ObjectiveId)
+                .collect(),
+        };
+
+        let obj_metadata_list = match catch_objectives.obj_metadata.get() {
+            Some(obj_metadata_list) => obj_metadata_list,
+            None => return vec![],
+        };
+
+        candidates
+            .into_iter()
+            .filter_map(|obj_id| {
+                let objective = catch_objectives.get_objective(obj_id);
+
+                if filter
+                    .tag
+                    .as_ref()
+                    .is_some_and(|tag| !objective.tags.contains(tag))
+                {
+                    return None;
+                }
+
+                let obj_status = catch_objectives.objective_status(&objective);
+                if filter.status.is_some_and(|status| status != obj_status) {
+                    return None;
+                }
+
+                let obj_stat = catch_objectives.stats.get(obj_id.into())?;
+                if filter
+                    .rarity
+                    .is_some_and(|rarity| rarity != obj_stat.rarity)
+                {
+                    return None;
+                }
+
+                catch_objectives.json_objective(
+                    obj_id,
+                    objective,
+                    obj_stat,
+                    obj_status,
+                    &obj_metadata_list,
+                )
+            })
+            .collect()
+    }
+
+    /// View Function - returns paginated view of the categories along with the number of Objectives in each
+    pub fn get_category_counts(&self, from_index: u64, limit: u64) -> Vec<(String, u64)> {
+        let categories = &self.catch_objectives.categories.objectives;
+        let (keys, values) = (categories.keys_as_vector(), categories.values_as_vector());
+
+        (from_index..std::cmp::min(from_index.saturating_add(limit), keys.len()))
+            .filter_map(|index| Some((keys.get(index)?, values.get(index)?.len())))
+            .collect()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod categories_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    fn ids(json_objs: Vec<JsonObjective>) -> Vec<ObjectiveId> {
+        json_objs.iter().map(|json_obj| json_obj.id).collect()
+    }
+
+    fn create_labelled_contract() -> Contract {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.set_objective_labels(
+            0,
+            Some("combat".to_string()),
+            vec!["pvp".to_string(), "weekly".to_string()],
+        );
+        contract.set_objective_labels(1, Some("explore".to_string()), vec!["weekly".to_string()]);
+
+        contract
+    }
+
+    #[test]
+    fn get_objectives_by() {
+        let contract = create_labelled_contract();
+
+        let by_category = ObjectiveFilter {
+            category: Some("combat".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ids(contract.get_objectives_by(by_category, 0, 10)), vec![0]);
+
+        let by_tag = ObjectiveFilter {
+            tag: Some("weekly".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(contract.get_objectives_by(by_tag.clone(), 0, 10)),
+            vec![0, 1]
+        );
+        assert_eq!(
+            ids(contract.get_objectives_by(by_tag.clone(), 1, 10)),
+            vec![1]
+        );
+
+        // Page is taken from the category index, the tag is checked on its Objectives
+        let by_tag_and_category = ObjectiveFilter {
+            category: Some("explore".to_string()),
+            tag: Some("weekly".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(contract.get_objectives_by(by_tag_and_category, 0, 1)),
+            vec![1]
+        );
+        let by_tag_and_status = ObjectiveFilter {
+            status: Some(ObjectiveStatus::Active),
+            ..by_tag
+        };
+        assert_eq!(
+            ids(contract.get_objectives_by(by_tag_and_status, 1, 1)),
+            vec![1]
+        );
+
+        let by_category_and_tag = ObjectiveFilter {
+            category: Some("explore".to_string()),
+            tag: Some("pvp".to_string()),
+            ..Default::default()
+        };
+        assert!(contract
+            .get_objectives_by(by_category_and_tag, 0, 10)
+            .is_empty());
+
+        let by_rarity = ObjectiveFilter {
+            rarity: Some(Rarity::Legendary),
+            status: Some(ObjectiveStatus::Active),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(contract.get_objectives_by(by_rarity.clone(), 0, 10)),
+            vec![0, 1]
+        );
+        assert_eq!(ids(contract.get_objectives_by(by_rarity, 1, 1)), vec![1]);
+    }
+
+    #[test]
+    fn get_category_counts() {
+        let mut contract = create_labelled_contract();
+
+        assert_eq!(
+            contract.get_category_counts(0, 10),
+            vec![("combat".to_string(), 1), ("explore".to_string(), 1)]
+        );
+        assert_eq!(
+            contract.get_category_counts(1, 10),
+            vec![("explore".to_string(), 1)]
+        );
+
+        contract.set_objective_labels(1, Some("combat".to_string()), vec![]);
+
+        assert_eq!(
+            contract.get_category_counts(0, 10),
+            vec![("combat".to_string(), 2)]
+        );
+
+        let by_tag = ObjectiveFilter {
+            tag: Some("weekly".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ids(contract.get_objectives_by(by_tag, 0, 10)), vec![0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate tag")]
+    fn set_objective_labels_duplicate_tag() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.set_objective_labels(0, None, vec!["pvp".to_string(), "pvp".to_string()]);
+    }
+}
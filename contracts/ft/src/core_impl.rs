@@ -12,6 +12,24 @@ pub struct FungibleToken {
 
     /// Total supply of the FT token.
     pub total_supply: Balance,
+
+    /// Cap on the total supply set at init, None means no cap
+    pub max_supply: Option<Balance>,
+
+    /// Number of accounts registered with the FT contract.
+    pub registered_accounts: u64,
+
+    /// AccountID -> Locked tokens vesting into the account balance, they are not part of the balance until claimed
+    pub vesting: LookupMap<AccountId, Vec<VestingGrant>>,
+
+    /// (Owner, Spender) -> Tokens the spender can transfer on behalf of the owner
+    pub allowances: LookupMap<(AccountId, AccountId), Allowance>,
+
+    /// AccountID -> Storage deposit paying for the allowances given by the account
+    pub storage_accounts: LookupMap<AccountId, StorageAccount>,
+
+    /// AccountID -> Compliance freeze blocking the transfers of the account
+    pub frozen_accounts: UnorderedMap<AccountId, AccountFreeze>,
 }
 
 pub trait FungibleTokenCore {
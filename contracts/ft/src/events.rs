@@ -6,6 +6,8 @@
 // //!
 // //! The three events in this standard are [`FtMint`], [`FtTransfer`], and [`FtBurn`].
 // //!
+// //! Catch specific events (Objectives, Rewards ...) are logged under the `catch` standard.
+// //!
 
 use std::fmt;
 
@@ -15,6 +17,10 @@ pub const FT_STANDARD_NAME: &str = "nep141";
 
 pub const FT_METADATA_SPEC: &str = "1.0.0";
 
+pub const CATCH_STANDARD_NAME: &str = "catch";
+
+pub const CATCH_EVENT_VERSION: &str = "1.0.0";
+
 /// Enum that represents the data type of the EventLog.
 #[derive(Serialize, Debug)]
 #[serde(tag = "event", content = "data")]
@@ -25,6 +31,41 @@ pub enum EventLogVariant {
     FtMint(FtMintLog),
     FtTransfer(FtTransferLog),
     FtBurn(FtBurnLog),
+    ObjectiveAdd(ObjectiveAddLog),
+    ObjectiveUpdate(ObjectiveUpdateLog),
+    ObjectiveRetire(ObjectiveRetireLog),
+    ObjectiveRarityUpdate(ObjectiveRarityUpdateLog),
+    PlayerReward(PlayerRewardLog),
+    RoleGrant(RoleGrantLog),
+    RoleRevoke(RoleRevokeLog),
+    RewardPoolFund(RewardPoolFundLog),
+    RewardPoolWithdraw(RewardPoolWithdrawLog),
+    EpochBudgetUpdate(EpochBudgetUpdateLog),
+    ObjectiveBudgetUpdate(ObjectiveBudgetUpdateLog),
+    RewardBudgetExhausted(RewardBudgetExhaustedLog),
+    SeasonUpdate(SeasonUpdateLog),
+    ObjectiveScheduleUpdate(ObjectiveScheduleUpdateLog),
+    ObjectivePrerequisitesUpdate(ObjectivePrerequisitesUpdateLog),
+    ObjectiveRewardCurveUpdate(ObjectiveRewardCurveUpdateLog),
+    ObjectiveVestingUpdate(ObjectiveVestingUpdateLog),
+    CampaignCreate(CampaignCreateLog),
+    CampaignClaim(CampaignClaimLog),
+    CampaignClose(CampaignCloseLog),
+    VoucherKeyAdd(VoucherKeyAddLog),
+    VoucherKeyRemove(VoucherKeyRemoveLog),
+    VoucherClaim(VoucherClaimLog),
+    ObjectiveLabelsUpdate(ObjectiveLabelsUpdateLog),
+    SponsorshipCreate(SponsorshipCreateLog),
+    SponsorshipWithdraw(SponsorshipWithdrawLog),
+    SponsorshipPropose(SponsorshipProposeLog),
+    SponsorshipReject(SponsorshipRejectLog),
+    ObjectiveLocationUpdate(ObjectiveLocationUpdateLog),
+    AllowanceUpdate(AllowanceUpdateLog),
+    AllowanceRevoke(AllowanceRevokeLog),
+    PauseUpdate(PauseUpdateLog),
+    KycUpdate(KycUpdateLog),
+    AccountFreeze(AccountFreezeLog),
+    AccountUnfreeze(AccountUnfreezeLog),
 }
 
 #[derive(Serialize, Debug)]
@@ -38,6 +79,19 @@ pub struct EventLog {
     pub event: EventLogVariant,
 }
 
+impl EventLog {
+    /// Logs a Catch specific event
+    fn emit_catch_event(event: EventLogVariant) {
+        let event_log = EventLog {
+            standard: CATCH_STANDARD_NAME.to_string(),
+            version: CATCH_EVENT_VERSION.to_string(),
+            event,
+        };
+
+        env::log(event_log.to_string().as_bytes());
+    }
+}
+
 impl fmt::Display for EventLog {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_fmt(format_args!(
@@ -111,9 +165,536 @@ impl FtBurnLog {
     }
 }
 
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectiveAddLog {
+    pub obj_id: ObjectiveId,
+    pub title: String,
+    pub reward: U128,
+}
+
+impl ObjectiveAddLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::ObjectiveAdd(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectiveUpdateLog {
+    pub obj_id: ObjectiveId,
+    pub title: String,
+    pub reward: U128,
+    pub reference: String,
+}
+
+impl ObjectiveUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::ObjectiveUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectiveRetireLog {
+    pub obj_id: ObjectiveId,
+}
+
+impl ObjectiveRetireLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::ObjectiveRetire(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectiveRarityUpdateLog {
+    pub obj_id: ObjectiveId,
+    pub old_rarity: Rarity,
+    pub new_rarity: Rarity,
+}
+
+impl ObjectiveRarityUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::ObjectiveRarityUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PlayerRewardLog {
+    pub player_id: AccountId,
+    pub obj_ids: Vec<ObjectiveId>,
+    /// Reward paid for each of the obj_ids, in the same order
+    pub rewards: Vec<U128>,
+    /// Rarity of each of the obj_ids after this reward, in the same order
+    pub rarities: Vec<Rarity>,
+    pub total: U128,
+    /// Part of the total which is locked for vesting
+    pub locked: U128,
+}
+
+impl PlayerRewardLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::PlayerReward(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleGrantLog {
+    pub role: Role,
+    pub account_id: AccountId,
+    pub sender_id: AccountId,
+}
+
+impl RoleGrantLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::RoleGrant(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleRevokeLog {
+    pub role: Role,
+    pub account_id: AccountId,
+    pub sender_id: AccountId,
+}
+
+impl RoleRevokeLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::RoleRevoke(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardPoolFundLog {
+    pub account_id: AccountId,
+    pub amount: U128,
+    /// Balance of the reward pool after funding
+    pub balance: U128,
+}
+
+impl RewardPoolFundLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::RewardPoolFund(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardPoolWithdrawLog {
+    pub receiver_id: AccountId,
+    pub amount: U128,
+    /// Balance of the reward pool after the withdrawal
+    pub balance: U128,
+    pub sender_id: AccountId,
+}
+
+impl RewardPoolWithdrawLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::RewardPoolWithdraw(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EpochBudgetUpdateLog {
+    pub epoch_duration: U64,
+    pub epoch_budget: Option<U128>,
+    pub sender_id: AccountId,
+}
+
+impl EpochBudgetUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::EpochBudgetUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectiveBudgetUpdateLog {
+    pub obj_id: ObjectiveId,
+    pub budget: Option<U128>,
+    pub sender_id: AccountId,
+}
+
+impl ObjectiveBudgetUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::ObjectiveBudgetUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardBudgetExhaustedLog {
+    /// Exhausted budget, one of "pool", "epoch" or "objective"
+    pub budget: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obj_id: Option<ObjectiveId>,
+    pub requested: U128,
+    pub remaining: U128,
+}
+
+impl RewardBudgetExhaustedLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::RewardBudgetExhausted(self));
+    }
+
+    /// Reason the reward fails with
+    pub fn reason(&self) -> &'static str {
+        match self.budget.as_str() {
+            "objective" => "Objective reward budget exhausted",
+            "epoch" => "Epoch reward budget exhausted",
+            "sponsorship" => "Sponsorship doesn't have enough balance",
+            _ => "Reward pool doesn't have enough balance",
+        }
+    }
+
+    /// Logs of a failed receipt are dropped, so the event is emitted only where the request is skipped instead of panicking
+    pub fn emit_and_reason(self) -> String {
+        let reason = self.reason().to_string();
+        self.emit();
+        reason
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeasonUpdateLog {
+    pub season_id: String,
+    pub starts_at: Option<U64>,
+    pub ends_at: Option<U64>,
+}
+
+impl SeasonUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::SeasonUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectiveScheduleUpdateLog {
+    pub obj_id: ObjectiveId,
+    pub starts_at: Option<U64>,
+    pub ends_at: Option<U64>,
+    pub season_id: Option<String>,
+}
+
+impl ObjectiveScheduleUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::ObjectiveScheduleUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectivePrerequisitesUpdateLog {
+    pub obj_id: ObjectiveId,
+    pub prerequisites: Vec<ObjectiveId>,
+    pub quest_line: Option<String>,
+}
+
+impl ObjectivePrerequisitesUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::ObjectivePrerequisitesUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectiveRewardCurveUpdateLog {
+    pub obj_id: ObjectiveId,
+    pub reward_curve: RewardCurve,
+}
+
+impl ObjectiveRewardCurveUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::ObjectiveRewardCurveUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectiveVestingUpdateLog {
+    pub obj_id: ObjectiveId,
+    pub vesting: Option<VestingSchedule>,
+}
+
+impl ObjectiveVestingUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::ObjectiveVestingUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CampaignCreateLog {
+    pub campaign_id: CampaignId,
+    pub budget: U128,
+    pub expires_at: U64,
+}
+
+impl CampaignCreateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::CampaignCreate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CampaignClaimLog {
+    pub campaign_id: CampaignId,
+    pub index: u64,
+    pub account_id: AccountId,
+    pub amount: U128,
+}
+
+impl CampaignClaimLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::CampaignClaim(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CampaignCloseLog {
+    pub campaign_id: CampaignId,
+    /// Tokens returned to the reward pool
+    pub unclaimed: U128,
+}
+
+impl CampaignCloseLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::CampaignClose(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VoucherKeyAddLog {
+    pub public_key: Base58PublicKey,
+    pub sender_id: AccountId,
+}
+
+impl VoucherKeyAddLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::VoucherKeyAdd(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VoucherKeyRemoveLog {
+    pub public_key: Base58PublicKey,
+    pub sender_id: AccountId,
+}
+
+impl VoucherKeyRemoveLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::VoucherKeyRemove(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VoucherClaimLog {
+    pub player_id: AccountId,
+    pub obj_id: ObjectiveId,
+    pub amount: U128,
+    pub nonce: U64,
+    /// Key which signed the voucher
+    pub public_key: Base58PublicKey,
+}
+
+impl VoucherClaimLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::VoucherClaim(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectiveLabelsUpdateLog {
+    pub obj_id: ObjectiveId,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl ObjectiveLabelsUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::ObjectiveLabelsUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorshipCreateLog {
+    pub obj_id: ObjectiveId,
+    pub sponsor_id: AccountId,
+    pub amount: U128,
+}
+
+impl SponsorshipCreateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::SponsorshipCreate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorshipWithdrawLog {
+    pub obj_id: ObjectiveId,
+    pub sponsor_id: AccountId,
+    pub amount: U128,
+}
+
+impl SponsorshipWithdrawLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::SponsorshipWithdraw(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorshipProposeLog {
+    pub proposal_id: ProposalId,
+    pub sponsor_id: AccountId,
+    pub amount: U128,
+}
+
+impl SponsorshipProposeLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::SponsorshipPropose(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorshipRejectLog {
+    pub proposal_id: ProposalId,
+    pub sponsor_id: AccountId,
+    pub amount: U128,
+    pub sender_id: AccountId,
+}
+
+impl SponsorshipRejectLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::SponsorshipReject(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ObjectiveLocationUpdateLog {
+    pub obj_id: ObjectiveId,
+    pub location: Option<GeoLocation>,
+}
+
+impl ObjectiveLocationUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::ObjectiveLocationUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AllowanceUpdateLog {
+    pub owner_id: AccountId,
+    pub spender_id: AccountId,
+    /// Remaining allowance after the change
+    pub amount: U128,
+    pub expires_at: Option<U64>,
+}
+
+impl AllowanceUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::AllowanceUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AllowanceRevokeLog {
+    pub owner_id: AccountId,
+    pub spender_id: AccountId,
+}
+
+impl AllowanceRevokeLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::AllowanceRevoke(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseUpdateLog {
+    pub flags: Vec<PauseFlag>,
+    pub paused: bool,
+    pub reason: String,
+    pub sender_id: AccountId,
+}
+
+impl PauseUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::PauseUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct KycUpdateLog {
+    pub account_id: AccountId,
+    pub status: KycStatus,
+    pub level: u8,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<U64>,
+
+    pub sender_id: AccountId,
+}
+
+impl KycUpdateLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::KycUpdate(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccountFreezeLog {
+    pub account_id: AccountId,
+    pub direction: FreezeDirection,
+    pub reason_code: String,
+    pub sender_id: AccountId,
+}
+
+impl AccountFreezeLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::AccountFreeze(self));
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccountUnfreezeLog {
+    pub account_id: AccountId,
+    pub reason_code: String,
+    pub sender_id: AccountId,
+}
+
+impl AccountUnfreezeLog {
+    pub fn emit(self) {
+        EventLog::emit_catch_event(EventLogVariant::AccountUnfreeze(self));
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
-mod events {
+mod events_tests {
     use super::*;
     use crate::utils::test_utils::*;
     use near_sdk::json_types::U128;
@@ -127,7 +708,7 @@ mod events {
         let amount = U128(100);
         FtMintLog {
             owner_id: owner_id.to_string(),
-            amount: amount,
+            amount,
             memo: None,
         }
         .emit();
@@ -144,7 +725,7 @@ mod events {
         let amount = U128(100);
         FtBurnLog {
             owner_id: owner_id.to_string(),
-            amount: amount,
+            amount,
             memo: None,
         }
         .emit();
@@ -163,7 +744,7 @@ mod events {
         FtTransferLog {
             old_owner_id: old_owner_id.to_string(),
             new_owner_id: new_owner_id.to_string(),
-            amount: amount,
+            amount,
             memo: None,
         }
         .emit();
@@ -172,4 +753,14 @@ mod events {
             r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":{"old_owner_id":"bob.near","new_owner_id":"alice.near","amount":"100"}}"#
         );
     }
+
+    #[test]
+    fn objective_retire() {
+        testing_env!(get_context(carol().to_string(), 500));
+        ObjectiveRetireLog { obj_id: 1 }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"catch","version":"1.0.0","event":"objective_retire","data":{"obj_id":1}}"#
+        );
+    }
 }
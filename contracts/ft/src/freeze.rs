@@ -0,0 +1,314 @@
+use crate::*;
+
+/// Transfers blocked by a freeze
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum FreezeDirection {
+    /// Tokens can't leave the account, it can still receive tokens
+    Outgoing,
+
+    /// Tokens can't enter the account, rewards included
+    Incoming,
+
+    Both,
+}
+
+impl FreezeDirection {
+    pub fn blocks_outgoing(&self) -> bool {
+        matches!(self, FreezeDirection::Outgoing | FreezeDirection::Both)
+    }
+
+    pub fn blocks_incoming(&self) -> bool {
+        matches!(self, FreezeDirection::Incoming | FreezeDirection::Both)
+    }
+}
+
+/// Compliance hold on an account, the balance stays with the account
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct AccountFreeze {
+    pub direction: FreezeDirection,
+
+    /// Reference of the legal request or internal case, like "LR-2024-017"
+    pub reason_code: String,
+
+    pub frozen_at: u64,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FrozenAccountView {
+    pub account_id: AccountId,
+    pub direction: FreezeDirection,
+    pub reason_code: String,
+    pub frozen_at: U64,
+}
+
+/// Max length in bytes of a reason code
+const MAX_REASON_CODE_LENGTH: usize = 64;
+
+impl FungibleToken {
+    pub fn is_frozen_outgoing(&self, account_id: &AccountId) -> bool {
+        self.frozen_accounts
+            .get(account_id)
+            .is_some_and(|freeze| freeze.direction.blocks_outgoing())
+    }
+
+    pub fn is_frozen_incoming(&self, account_id: &AccountId) -> bool {
+        self.frozen_accounts
+            .get(account_id)
+            .is_some_and(|freeze| freeze.direction.blocks_incoming())
+    }
+
+    pub fn assert_can_send(&self, account_id: &AccountId) {
+        require!(
+            !self.is_frozen_outgoing(account_id),
+            format!("Account {} is frozen for outgoing transfers", account_id)
+        );
+    }
+
+    pub fn assert_can_receive(&self, account_id: &AccountId) {
+        require!(
+            !self.is_frozen_incoming(account_id),
+            format!("Account {} is frozen for incoming transfers", account_id)
+        );
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Admin Only - Freeze the transfers of an account in the given direction, freezing again replaces the previous freeze
+    pub fn freeze_account(
+        &mut self,
+        account_id: ValidAccountId,
+        direction: FreezeDirection,
+        reason_code: String,
+    ) {
+        self.assert_role(Role::Admin);
+        assert_valid_reason_code(&reason_code);
+
+        let account_id: AccountId = account_id.into();
+
+        // Contract account holds the reward pool and the escrows, freezing it would stop every payout
+        require!(
+            account_id != env::current_account_id(),
+            "Contract account can't be frozen"
+        );
+
+        self.token.frozen_accounts.insert(
+            &account_id,
+            &AccountFreeze {
+                direction,
+                reason_code: reason_code.clone(),
+                frozen_at: env::block_timestamp(),
+            },
+        );
+
+        AccountFreezeLog {
+            account_id,
+            direction,
+            reason_code,
+            sender_id: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    /// Admin Only - Lift the freeze of an account
+    pub fn unfreeze_account(&mut self, account_id: ValidAccountId, reason_code: String) {
+        self.assert_role(Role::Admin);
+        assert_valid_reason_code(&reason_code);
+
+        let account_id: AccountId = account_id.into();
+
+        require!(
+            self.token.frozen_accounts.remove(&account_id).is_some(),
+            "Account is not frozen"
+        );
+
+        AccountUnfreezeLog {
+            account_id,
+            reason_code,
+            sender_id: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    /// View Function - returns the freeze of the account, None if it isn't frozen
+    pub fn get_account_freeze(&self, account_id: ValidAccountId) -> Option<FrozenAccountView> {
+        let account_id: AccountId = account_id.into();
+
+        self.token
+            .frozen_accounts
+            .get(&account_id)
+            .map(|freeze| frozen_account_view(account_id, freeze))
+    }
+
+    /// View Function - returns paginated view of the frozen accounts
+    pub fn get_frozen_accounts(&self, from_index: u64, limit: u64) -> Vec<FrozenAccountView> {
+        self.token
+            .frozen_accounts
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .map(|(account_id, freeze)| frozen_account_view(account_id, freeze))
+            .collect()
+    }
+}
+
+fn assert_valid_reason_code(reason_code: &str) {
+    require!(
+        !reason_code.is_empty() && reason_code.len() <= MAX_REASON_CODE_LENGTH,
+        format!(
+            "Reason code should be 1 to {} bytes long",
+            MAX_REASON_CODE_LENGTH
+        )
+    );
+}
+
+fn frozen_account_view(account_id: AccountId, freeze: AccountFreeze) -> FrozenAccountView {
+    FrozenAccountView {
+        account_id,
+        direction: freeze.direction,
+        reason_code: freeze.reason_code,
+        frozen_at: U64::from(freeze.frozen_at),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod freeze_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    fn create_funded_contract() -> Contract {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        contract.storage_deposit(Some(bob()));
+        verify_players(&mut contract, &[carol(), bob()]);
+        contract
+            .token
+            .internal_transfer(&dex().into(), &carol().into(), 1000, None);
+
+        contract
+    }
+
+    #[test]
+    fn freeze_and_unfreeze() {
+        let mut contract = create_funded_contract();
+
+        contract.freeze_account(carol(), FreezeDirection::Outgoing, "LR-017".to_string());
+        contract.freeze_account(bob(), FreezeDirection::Both, "LR-018".to_string());
+        assert_eq!(
+            contract.get_frozen_accounts(1, 10),
+            vec![FrozenAccountView {
+                account_id: bob().to_string(),
+                direction: FreezeDirection::Both,
+                reason_code: "LR-018".to_string(),
+                frozen_at: U64::from(0),
+            }]
+        );
+
+        // Outgoing freeze still lets the account receive tokens
+        testing_env!(get_context(dex().to_string(), ONE_YOCTO));
+        contract.ft_transfer(carol(), U128::from(500), None);
+        assert_eq!(contract.ft_balance_of(carol()).0, 1500);
+
+        testing_env!(get_context(dex().to_string(), 0));
+        contract.unfreeze_account(carol(), "LR-017-CLOSED".to_string());
+        assert!(contract.get_account_freeze(carol()).is_none());
+        assert_eq!(contract.get_frozen_accounts(0, 10).len(), 1);
+
+        testing_env!(get_context(carol().to_string(), ONE_YOCTO));
+        contract.ft_transfer(dex(), U128::from(1500), None);
+        assert_eq!(contract.ft_balance_of(carol()).0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account carol.near is frozen for outgoing transfers")]
+    fn ft_transfer_frozen_sender() {
+        let mut contract = create_funded_contract();
+        contract.freeze_account(carol(), FreezeDirection::Both, "LR-017".to_string());
+
+        testing_env!(get_context(carol().to_string(), ONE_YOCTO));
+        contract.ft_transfer(bob(), U128::from(100), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account bob.near is frozen for incoming transfers")]
+    fn ft_transfer_frozen_receiver() {
+        let mut contract = create_funded_contract();
+        contract.freeze_account(bob(), FreezeDirection::Incoming, "LR-018".to_string());
+
+        testing_env!(get_context(carol().to_string(), ONE_YOCTO));
+        contract.ft_transfer(bob(), U128::from(100), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account carol.near is frozen for incoming transfers")]
+    fn transfer_player_reward_frozen() {
+        let mut contract = create_funded_contract();
+        seed_reward_pool(&mut contract, 5000);
+        contract.freeze_account(carol(), FreezeDirection::Incoming, "LR-017".to_string());
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Account carol.near is frozen for outgoing transfers")]
+    fn fund_reward_pool_frozen() {
+        let mut contract = create_funded_contract();
+        contract.freeze_account(carol(), FreezeDirection::Outgoing, "LR-017".to_string());
+
+        testing_env!(get_context(carol().to_string(), ONE_YOCTO));
+        contract.fund_reward_pool(U128::from(500));
+    }
+
+    #[test]
+    #[should_panic(expected = "Account bob.near is frozen for incoming transfers")]
+    fn withdraw_reward_pool_frozen() {
+        let mut contract = create_funded_contract();
+        seed_reward_pool(&mut contract, 5000);
+        contract.freeze_account(bob(), FreezeDirection::Incoming, "LR-018".to_string());
+
+        contract.withdraw_reward_pool(bob(), U128::from(1000));
+    }
+
+    #[test]
+    fn ft_resolve_transfer_frozen_receiver_keeps_refund() {
+        let mut contract = create_funded_contract();
+        contract.freeze_account(bob(), FreezeDirection::Outgoing, "LR-018".to_string());
+
+        // Tokens already moved to bob by ft_transfer_call(), the receiver contract failed
+        contract
+            .token
+            .internal_transfer(&carol().into(), &bob().into(), 400, None);
+        testing_env!(
+            get_context(env::current_account_id(), 0),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+
+        let used = contract.ft_resolve_transfer(carol().into(), bob().into(), U128::from(400));
+        assert_eq!(used.0, 400);
+        assert_eq!(contract.ft_balance_of(carol()).0, 600);
+        assert_eq!(contract.ft_balance_of(bob()).0, 400);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only accounts with Admin role can call this method")]
+    fn freeze_account_not_admin() {
+        let mut contract = create_funded_contract();
+
+        testing_env!(get_context(carol().to_string(), 0));
+        contract.freeze_account(bob(), FreezeDirection::Both, "LR-018".to_string());
+    }
+}
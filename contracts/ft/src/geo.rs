@@ -0,0 +1,418 @@
+use crate::*;
+
+use std::ops::RangeInclusive;
+
+use crate::catch_game::{JsonObjective, Objective};
+
+/// Coordinates are in microdegrees, i.e. degrees * 10^6
+const MAX_LATITUDE: i32 = 90_000_000;
+const MAX_LONGITUDE: i32 = 180_000_000;
+
+/// Max length in bytes of a region code, long enough for ISO 3166-2 codes like "IN-MH"
+const MAX_REGION_LENGTH: usize = 16;
+
+/// Side of a cell of the Catch Map grid in microdegrees, i.e. 1 degree
+const CELL_SIZE: i32 = 1_000_000;
+
+/// Place on the Catch Map where an Objective can be accomplished
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GeoLocation {
+    /// Latitude in microdegrees, -90_000_000 to 90_000_000
+    pub lat: i32,
+
+    /// Longitude in microdegrees, -180_000_000 to 180_000_000
+    pub lng: i32,
+
+    /// Radius in meters around the point within which the Objective can be accomplished
+    pub radius: u32,
+
+    /// Region code the location belongs to, like "IN-MH"
+    pub region: String,
+}
+
+impl GeoLocation {
+    pub fn assert_valid_location(&self) {
+        require!(
+            (-MAX_LATITUDE..=MAX_LATITUDE).contains(&self.lat),
+            "Latitude should be between -90 and 90 degrees"
+        );
+        require!(
+            (-MAX_LONGITUDE..=MAX_LONGITUDE).contains(&self.lng),
+            "Longitude should be between -180 and 180 degrees"
+        );
+        require!(
+            !self.region.is_empty() && self.region.len() <= MAX_REGION_LENGTH,
+            format!(
+                "Region code should be 1 to {} bytes long",
+                MAX_REGION_LENGTH
+            )
+        );
+    }
+
+    /// Bounding box is inclusive, west greater than east means it crosses the antimeridian
+    pub fn is_in_box(&self, south: i32, west: i32, north: i32, east: i32) -> bool {
+        let in_lng = if west <= east {
+            west <= self.lng && self.lng <= east
+        } else {
+            self.lng >= west || self.lng <= east
+        };
+
+        south <= self.lat && self.lat <= north && in_lng
+    }
+
+    /// (row, column) of the Catch Map grid cell containing the location
+    pub fn cell(&self) -> (i32, i32) {
+        (
+            self.lat.div_euclid(CELL_SIZE),
+            self.lng.div_euclid(CELL_SIZE),
+        )
+    }
+}
+
+/// Label of a grid cell in the cells index, like "19:72"
+fn cell_label((row, column): (i32, i32)) -> String {
+    format!("{}:{}", row, column)
+}
+
+fn parse_cell_label(label: &str) -> (i32, i32) {
+    let (row, column) = label
+        .split_once(':')
+        .unwrap_or_else(|| env::panic(b"Invariant : Invalid cell label"));
+
+    (row.parse().unwrap(), column.parse().unwrap())
+}
+
+/// Grid columns covered by the longitudes of a box, two ranges when it crosses the antimeridian
+fn column_ranges(west: i32, east: i32) -> Vec<RangeInclusive<i32>> {
+    let (west, east) = (west.div_euclid(CELL_SIZE), east.div_euclid(CELL_SIZE));
+
+    if west <= east {
+        vec![west..=east]
+    } else {
+        vec![
+            west..=MAX_LONGITUDE.div_euclid(CELL_SIZE),
+            (-MAX_LONGITUDE).div_euclid(CELL_SIZE)..=east,
+        ]
+    }
+}
+
+impl CatchObjectives {
+    /// Moves the Objective from the region and grid cell of old_location to the ones of new_location
+    pub fn index_location(
+        &mut self,
+        obj_id: ObjectiveId,
+        old_location: Option<&GeoLocation>,
+        new_location: Option<&GeoLocation>,
+    ) {
+        if let Some(location) = old_location {
+            self.regions.remove(&location.region, obj_id);
+            self.cells.remove(&cell_label(location.cell()), obj_id);
+        }
+        if let Some(location) = new_location {
+            self.regions.insert(&location.region, obj_id);
+            self.cells.insert(&cell_label(location.cell()), obj_id);
+        }
+    }
+
+    /// Objectives in the grid cells covered by the box in ascending order
+    ///
+    /// Covered cells are looked up one by one, unless there are more of them than occupied cells,
+    /// then the occupied cells are scanned instead
+    fn objectives_in_cells(
+        &self,
+        south: i32,
+        west: i32,
+        north: i32,
+        east: i32,
+    ) -> Vec<ObjectiveId> {
+        let rows = south.div_euclid(CELL_SIZE)..=north.div_euclid(CELL_SIZE);
+        let columns = column_ranges(west, east);
+
+        let covered_cells = rows.clone().count() as u64
+            * columns
+                .iter()
+                .map(|range| range.clone().count() as u64)
+                .sum::<u64>();
+
+        let mut obj_ids: Vec<ObjectiveId> = if covered_cells > self.cells.objectives.len() {
+            self.cells
+                .objectives
+                .iter()
+                .filter(|(label, _)| {
+                    let (row, column) = parse_cell_label(label);
+                    rows.contains(&row) && columns.iter().any(|range| range.contains(&column))
+                })
+                .flat_map(|(_, obj_ids)| obj_ids.to_vec())
+                .collect()
+        } else {
+            rows.flat_map(|row| {
+                columns
+                    .iter()
+                    .flat_map(move |range| range.clone().map(move |column| (row, column)))
+            })
+            .filter_map(|cell| self.cells.objectives.get(&cell_label(cell)))
+            .flat_map(|obj_ids| obj_ids.to_vec())
+            .collect()
+        };
+
+        obj_ids.sort_unstable();
+        obj_ids
+    }
+
+    /// Info of the Objectives in the order of obj_ids, skipping the ones whose location doesn't match
+    fn located_objectives(
+        &self,
+        obj_ids: impl Iterator<Item = ObjectiveId>,
+        matches: impl Fn(&GeoLocation) -> bool,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<JsonObjective> {
+        let obj_metadata_list = match self.obj_metadata.get() {
+            Some(obj_metadata_list) => obj_metadata_list,
+            None => return vec![],
+        };
+
+        obj_ids
+            .filter_map(|obj_id| {
+                let objective = self.get_objective(obj_id);
+
+                if !objective.location.as_ref().is_some_and(&matches) {
+                    return None;
+                }
+
+                let obj_status = self.objective_status(&objective);
+                let obj_stat = self.stats.get(obj_id.into())?;
+
+                self.json_objective(obj_id, objective, obj_stat, obj_status, &obj_metadata_list)
+            })
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+}
+
+impl Objective {
+    pub fn assert_valid_location(&self) {
+        if let Some(location) = &self.location {
+            location.assert_valid_location();
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// MetadataManager Only - Set the location of an Objective on the Catch Map, None removes it
+    pub fn set_objective_location(&mut self, obj_id: ObjectiveId, location: Option<GeoLocation>) {
+        self.assert_role(Role::MetadataManager);
+
+        let mut objective = self.catch_objectives.get_objective(obj_id);
+        let old_location = objective.location.take();
+
+        objective.location = location;
+        objective.assert_valid_location();
+
+        self.catch_objectives.index_location(
+            obj_id,
+            old_location.as_ref(),
+            objective.location.as_ref(),
+        );
+        self.catch_objectives
+            .objectives
+            .replace(obj_id.into(), &objective);
+
+        ObjectiveLocationUpdateLog {
+            obj_id,
+            location: objective.location,
+        }
+        .emit();
+    }
+
+    /// View Function - returns paginated view of the Objectives located in the bounding box, coordinates are in microdegrees
+    ///
+    /// west greater than east is a box crossing the antimeridian, from_index and limit apply to the matching Objectives
+    ///
+    /// Only the Objectives in the grid cells covered by the box are read
+    pub fn get_objectives_in_box(
+        &self,
+        south: i32,
+        west: i32,
+        north: i32,
+        east: i32,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<JsonObjective> {
+        require!(south <= north, "South can't be greater than north");
+
+        self.catch_objectives.located_objectives(
+            self.catch_objectives
+                .objectives_in_cells(south, west, north, east)
+                .into_iter(),
+            |location| location.is_in_box(south, west, north, east),
+            from_index,
+            limit,
+        )
+    }
+
+    /// View Function - returns paginated view of the Objectives located in the region
+    pub fn get_objectives_in_region(
+        &self,
+        region: String,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<JsonObjective> {
+        match self.catch_objectives.regions.objectives.get(&region) {
+            Some(obj_ids) => self.catch_objectives.located_objectives(
+                obj_ids.to_vec().into_iter(),
+                |_| true,
+                from_index,
+                limit,
+            ),
+            None => vec![],
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod geo_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    fn location(lat: i32, lng: i32, region: &str) -> GeoLocation {
+        GeoLocation {
+            lat,
+            lng,
+            radius: 50,
+            region: region.to_string(),
+        }
+    }
+
+    fn ids(json_objs: Vec<JsonObjective>) -> Vec<ObjectiveId> {
+        json_objs.iter().map(|json_obj| json_obj.id).collect()
+    }
+
+    /// Objective1 is in Mumbai and Objective2 is in Fiji, next to the antimeridian
+    fn create_located_contract() -> Contract {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.set_objective_location(0, Some(location(19_076_090, 72_877_426, "IN-MH")));
+        contract.set_objective_location(1, Some(location(-17_713_371, 178_065_032, "FJ-C")));
+
+        contract
+    }
+
+    #[test]
+    fn get_objectives_in_box() {
+        let contract = create_located_contract();
+
+        assert_eq!(
+            ids(contract
+                .get_objectives_in_box(18_000_000, 72_000_000, 20_000_000, 73_000_000, 0, 10)),
+            vec![0]
+        );
+
+        // Box crossing the antimeridian
+        assert_eq!(
+            ids(contract.get_objectives_in_box(
+                -20_000_000,
+                177_000_000,
+                -15_000_000,
+                -179_000_000,
+                0,
+                10
+            )),
+            vec![1]
+        );
+
+        assert_eq!(
+            ids(contract.get_objectives_in_box(
+                -90_000_000,
+                -180_000_000,
+                90_000_000,
+                180_000_000,
+                1,
+                10
+            )),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn objectives_are_indexed_by_cell() {
+        let mut contract = create_located_contract();
+
+        // Cells of negative coordinates are rounded down
+        assert_eq!(
+            location(-17_713_371, 178_065_032, "FJ-C").cell(),
+            (-18, 178)
+        );
+        assert_eq!(
+            contract.catch_objectives.objectives_in_cells(
+                -17_800_000,
+                178_000_000,
+                -17_000_000,
+                178_100_000
+            ),
+            vec![1]
+        );
+
+        // Objective1 moves to Pune, out of the cell of the Mumbai box
+        contract.set_objective_location(0, Some(location(18_520_430, 73_856_743, "IN-MH")));
+        assert!(contract
+            .get_objectives_in_box(19_000_000, 72_000_000, 20_000_000, 73_000_000, 0, 10)
+            .is_empty());
+        assert_eq!(
+            ids(contract
+                .get_objectives_in_box(18_000_000, 73_000_000, 19_000_000, 74_000_000, 0, 10)),
+            vec![0]
+        );
+        assert_eq!(contract.catch_objectives.cells.objectives.len(), 2);
+
+        // Objective in a covered cell but outside of the box
+        assert!(contract
+            .get_objectives_in_box(18_000_000, 73_000_000, 18_500_000, 74_000_000, 0, 10)
+            .is_empty());
+
+        contract.set_objective_location(0, None);
+        assert_eq!(contract.catch_objectives.cells.objectives.len(), 1);
+    }
+
+    #[test]
+    fn get_objectives_in_region() {
+        let mut contract = create_located_contract();
+
+        assert_eq!(
+            ids(contract.get_objectives_in_region("IN-MH".to_string(), 0, 10)),
+            vec![0]
+        );
+
+        contract.set_objective_location(1, Some(location(18_520_430, 73_856_743, "IN-MH")));
+        assert_eq!(
+            ids(contract.get_objectives_in_region("IN-MH".to_string(), 0, 10)),
+            vec![0, 1]
+        );
+        assert!(contract
+            .get_objectives_in_region("FJ-C".to_string(), 0, 10)
+            .is_empty());
+
+        contract.set_objective_location(0, None);
+        assert_eq!(
+            ids(contract.get_objectives_in_region("IN-MH".to_string(), 0, 10)),
+            vec![1]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Latitude should be between -90 and 90 degrees")]
+    fn set_objective_location_invalid() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.set_objective_location(0, Some(location(90_000_001, 0, "IN-MH")));
+    }
+}
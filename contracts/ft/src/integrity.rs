@@ -0,0 +1,180 @@
+use crate::*;
+
+use crate::catch_game::ObjectiveMetadata;
+
+/// Scheme every ObjectiveMetadata reference should use
+const REFERENCE_SCHEME: &str = "ipfs://";
+
+/// Result of check_objectives_integrity(), is_valid is false if any of the checks failed on the page
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntegrityReport {
+    pub is_valid: bool,
+
+    /// Lengths of the objectives, obj_metadata and stats vectors, they should all be equal
+    pub objective_count: u64,
+    pub metadata_count: u64,
+    pub stats_count: u64,
+
+    /// Objectives whose reference_hash is not 32 bytes
+    pub invalid_hashes: Vec<ObjectiveId>,
+
+    /// Objectives whose reference is not a well formed ipfs:// link
+    pub invalid_references: Vec<ObjectiveId>,
+}
+
+impl ObjectiveMetadata {
+    /// ipfs:// followed by a non empty path without whitespace
+    pub fn has_valid_reference(&self) -> bool {
+        match self.reference.strip_prefix(REFERENCE_SCHEME) {
+            Some(path) => !path.is_empty() && !path.chars().any(char::is_whitespace),
+            None => false,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// View Function - Verify that the Objectives, their metadata and stats are consistent, never panics on inconsistent data
+    ///
+    /// Vector lengths are always compared, metadata of limit Objectives starting at from_index is checked
+    pub fn check_objectives_integrity(&self, from_index: u64, limit: u64) -> IntegrityReport {
+        let catch_objectives = &self.catch_objectives;
+        let obj_metadata_list = catch_objectives.obj_metadata.get();
+
+        let mut report = IntegrityReport {
+            is_valid: true,
+            objective_count: catch_objectives.objectives.len(),
+            metadata_count: obj_metadata_list.as_ref().map_or(0, |list| list.len()),
+            stats_count: catch_objectives.stats.len(),
+            invalid_hashes: vec![],
+            invalid_references: vec![],
+        };
+
+        if let Some(obj_metadata_list) = obj_metadata_list {
+            let end_index =
+                std::cmp::min(from_index.saturating_add(limit), obj_metadata_list.len());
+
+            for obj_id in from_index..end_index {
+                let obj_metadata = obj_metadata_list.get(obj_id).unwrap();
+
+                if obj_metadata.reference_hash.0.len() != 32 {
+                    report.invalid_hashes.push(obj_id as ObjectiveId);
+                }
+                if !obj_metadata.has_valid_reference() {
+                    report.invalid_references.push(obj_id as ObjectiveId);
+                }
+            }
+        }
+
+        report.is_valid = report.metadata_count == report.objective_count
+            && report.stats_count == report.objective_count
+            && report.invalid_hashes.is_empty()
+            && report.invalid_references.is_empty();
+
+        report
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod integrity_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    #[test]
+    fn has_valid_reference() {
+        let metadata = |reference: &str| ObjectiveMetadata {
+            reference: reference.to_string(),
+            reference_hash: Base64VecU8::from([1_u8; 32].to_vec()),
+        };
+
+        assert!(metadata("ipfs://achievement1").has_valid_reference());
+        assert!(!metadata("ipfs://").has_valid_reference());
+        assert!(!metadata("https://achievement1").has_valid_reference());
+        assert!(!metadata("ipfs://achievement 1").has_valid_reference());
+    }
+
+    #[test]
+    fn check_objectives_integrity() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        assert!(contract.check_objectives_integrity(0, 10).is_valid);
+
+        // Corrupt the metadata of Objective1 and drop the metadata of Objective2
+        let mut obj_metadata_list = contract.catch_objectives.obj_metadata.get().unwrap();
+        obj_metadata_list.pop();
+        obj_metadata_list.replace(
+            0,
+            &ObjectiveMetadata {
+                reference: "achievement1".to_string(),
+                reference_hash: Base64VecU8::from([1_u8; 16].to_vec()),
+            },
+        );
+        contract
+            .catch_objectives
+            .obj_metadata
+            .set(&obj_metadata_list);
+
+        assert_eq!(
+            contract.check_objectives_integrity(0, 10),
+            IntegrityReport {
+                is_valid: false,
+                objective_count: 2,
+                metadata_count: 1,
+                stats_count: 2,
+                invalid_hashes: vec![0],
+                invalid_references: vec![0],
+            }
+        );
+
+        assert_eq!(
+            contract.check_objectives_integrity(1, 10),
+            IntegrityReport {
+                is_valid: false,
+                objective_count: 2,
+                metadata_count: 1,
+                stats_count: 2,
+                invalid_hashes: vec![],
+                invalid_references: vec![],
+            }
+        );
+
+        // Objectives without metadata are skipped instead of panicking
+        assert_eq!(contract.get_objectives(0, 10, None).len(), 1);
+        assert!(contract.get_objective(1).is_none());
+    }
+
+    #[test]
+    fn check_objectives_integrity_pages() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+
+        let mut obj_metadata_list = contract.catch_objectives.obj_metadata.get().unwrap();
+        obj_metadata_list.replace(
+            1,
+            &ObjectiveMetadata {
+                reference: "ipfs://achievement2".to_string(),
+                reference_hash: Base64VecU8::from([1_u8; 16].to_vec()),
+            },
+        );
+        contract
+            .catch_objectives
+            .obj_metadata
+            .set(&obj_metadata_list);
+
+        // Anyone can check, the invalid hash is only reported by the page having it
+        testing_env!(get_context(carol().to_string(), 0));
+        assert!(contract.check_objectives_integrity(0, 1).is_valid);
+
+        let report = contract.check_objectives_integrity(1, 1);
+        assert!(!report.is_valid);
+        assert_eq!(report.invalid_hashes, vec![1]);
+        assert!(report.invalid_references.is_empty());
+    }
+}
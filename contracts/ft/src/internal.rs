@@ -5,14 +5,50 @@ use crate::*;
 /**********************************************/
 
 impl FungibleToken {
+    /// Wraps existing account balances with empty vesting, allowances, storage deposits and freezes
+    pub fn new(
+        accounts: LookupMap<AccountId, Balance>,
+        total_supply: Balance,
+        registered_accounts: u64,
+        max_supply: Option<Balance>,
+    ) -> Self {
+        Self {
+            accounts,
+            total_supply,
+            max_supply,
+            registered_accounts,
+            vesting: LookupMap::new(StorageKey::Vesting.try_to_vec().unwrap()),
+            allowances: LookupMap::new(StorageKey::Allowances.try_to_vec().unwrap()),
+            storage_accounts: LookupMap::new(StorageKey::StorageAccounts.try_to_vec().unwrap()),
+            frozen_accounts: UnorderedMap::new(StorageKey::FrozenAccounts.try_to_vec().unwrap()),
+        }
+    }
+
+    pub fn internal_register_account(&mut self, account_id: &AccountId) {
+        if self.accounts.insert(account_id, &0).is_some() {
+            env::panic(b"The account is already registered");
+        }
+        self.registered_accounts += 1;
+    }
+
+    /// Removes the account and returns the balance it had
+    pub fn internal_unregister_account(&mut self, account_id: &AccountId) -> Balance {
+        let balance = self
+            .accounts
+            .remove(account_id)
+            .unwrap_or_else(|| env::panic(b"The account is not registered"));
+        self.registered_accounts -= 1;
+        balance
+    }
+
     pub fn internal_deposit(&mut self, account_id: &AccountId, amount: Balance) {
         let balance = self
             .accounts
-            .get(&account_id)
+            .get(account_id)
             .unwrap_or_else(|| env::panic(b"The account is not registered"));
 
         if let Some(new_balance) = balance.checked_add(amount) {
-            self.accounts.insert(&account_id, &new_balance);
+            self.accounts.insert(account_id, &new_balance);
         } else {
             env::panic(b"Balance overflow");
         }
@@ -21,11 +57,11 @@ impl FungibleToken {
     pub fn internal_withdraw(&mut self, account_id: &AccountId, amount: Balance) {
         let balance = self
             .accounts
-            .get(&account_id)
+            .get(account_id)
             .unwrap_or_else(|| env::panic(b"The account is not registered"));
 
         if let Some(new_balance) = balance.checked_sub(amount) {
-            self.accounts.insert(&account_id, &new_balance);
+            self.accounts.insert(account_id, &new_balance);
         } else {
             env::panic(b"The account doesn't have enough balance");
         }
@@ -45,6 +81,9 @@ impl FungibleToken {
 
         require!(amount > 0, "The amount should be a positive number");
 
+        self.assert_can_send(sender_id);
+        self.assert_can_receive(receiver_id);
+
         self.internal_withdraw(sender_id, amount);
         self.internal_deposit(receiver_id, amount);
 
@@ -76,18 +115,26 @@ impl FungibleToken {
             PromiseResult::Failed => amount,
         };
 
+        // Refund can't move tokens out of a frozen receiver or into a frozen sender, the receiver keeps them
+        if unused_amount > 0
+            && (self.is_frozen_outgoing(receiver_id) || self.is_frozen_incoming(sender_id))
+        {
+            log!("Refund is held as the account is frozen");
+            return amount;
+        }
+
         if unused_amount > 0 {
-            let receiver_balance = self.accounts.get(&receiver_id).unwrap_or(0);
+            let receiver_balance = self.accounts.get(receiver_id).unwrap_or(0);
 
             if receiver_balance > 0 {
                 let refund_amount = std::cmp::min(receiver_balance, unused_amount);
 
                 self.accounts
-                    .insert(&receiver_id, &(receiver_balance - refund_amount));
+                    .insert(receiver_id, &(receiver_balance - refund_amount));
 
-                if let Some(sender_balance) = self.accounts.get(&sender_id) {
+                if let Some(sender_balance) = self.accounts.get(sender_id) {
                     self.accounts
-                        .insert(&sender_id, &(sender_balance + refund_amount));
+                        .insert(sender_id, &(sender_balance + refund_amount));
 
                     FtTransferLog {
                         old_owner_id: receiver_id.to_string(),
@@ -96,7 +143,7 @@ impl FungibleToken {
                         memo: Some("refund".to_string()),
                     }
                     .emit();
-                    return (amount - refund_amount).into();
+                    return amount - refund_amount;
                 } else {
                     // Sender's account was deleted, so we need to burn tokens.
                     self.total_supply -= refund_amount;
@@ -0,0 +1,290 @@
+use crate::*;
+
+/// KYC status of an account as recorded by a KycVerifier
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum KycStatus {
+    /// Documents are submitted but not checked yet
+    Pending,
+
+    /// Account can receive rewards and own a player sub-account
+    Verified,
+
+    /// Account is no longer eligible for rewards, its balance is left untouched
+    Revoked,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct KycRecord {
+    pub status: KycStatus,
+
+    /// Level of verification, meaning of the levels is decided by the verifier
+    pub level: u8,
+
+    /// Verified status lapses at this timestamp, None if it never lapses
+    pub expires_at: Option<u64>,
+
+    pub updated_at: u64,
+}
+
+impl KycRecord {
+    pub fn is_verified(&self) -> bool {
+        self.status == KycStatus::Verified
+            && self
+                .expires_at
+                .is_none_or(|expires_at| env::block_timestamp() < expires_at)
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct KycView {
+    pub status: KycStatus,
+    pub level: u8,
+    pub expires_at: Option<U64>,
+    pub updated_at: U64,
+
+    /// false for a Verified record which has expired
+    pub is_verified: bool,
+}
+
+/// Account -> KYC record, accounts without a record are unverified
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct KycRegistry {
+    pub records: LookupMap<AccountId, KycRecord>,
+}
+
+impl Default for KycRegistry {
+    fn default() -> Self {
+        Self {
+            records: LookupMap::new(StorageKey::Kyc.try_to_vec().unwrap()),
+        }
+    }
+}
+
+impl KycRegistry {
+    pub fn is_verified(&self, account_id: &AccountId) -> bool {
+        self.records
+            .get(account_id)
+            .is_some_and(|record| record.is_verified())
+    }
+
+    pub fn set_record(
+        &mut self,
+        account_id: &AccountId,
+        status: KycStatus,
+        level: u8,
+        expires_at: Option<u64>,
+    ) {
+        self.records.insert(
+            account_id,
+            &KycRecord {
+                status,
+                level,
+                expires_at,
+                updated_at: env::block_timestamp(),
+            },
+        );
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// KycVerifier Only - Record the KYC status, level and expiry of an account
+    ///
+    /// Revoking stops the rewards of the account without touching its balance
+    pub fn set_kyc(
+        &mut self,
+        account_id: ValidAccountId,
+        status: KycStatus,
+        level: u8,
+        expires_at: Option<U64>,
+    ) {
+        self.assert_role(Role::KycVerifier);
+
+        let account_id: AccountId = account_id.into();
+        let expires_at: Option<u64> = expires_at.map(|expires_at| expires_at.into());
+
+        require!(
+            expires_at.is_none_or(|expires_at| expires_at > env::block_timestamp()),
+            "KYC should expire in the future"
+        );
+
+        self.kyc.set_record(&account_id, status, level, expires_at);
+
+        KycUpdateLog {
+            account_id,
+            status,
+            level,
+            expires_at: expires_at.map(U64::from),
+            sender_id: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    /// View Function - returns the KYC record of the account, None if it was never recorded
+    pub fn get_kyc(&self, account_id: ValidAccountId) -> Option<KycView> {
+        self.kyc
+            .records
+            .get(account_id.as_ref())
+            .map(|record| KycView {
+                status: record.status,
+                level: record.level,
+                expires_at: record.expires_at.map(U64::from),
+                updated_at: U64::from(record.updated_at),
+                is_verified: record.is_verified(),
+            })
+    }
+
+    /// View Function - returns true if the account is verified and the verification hasn't expired
+    pub fn is_kyc_verified(&self, account_id: ValidAccountId) -> bool {
+        self.kyc.is_verified(account_id.as_ref())
+    }
+}
+
+impl Contract {
+    pub fn assert_kyc_verified(&self, account_id: &AccountId) {
+        require!(
+            self.kyc.is_verified(account_id),
+            format!("Account {} is not KYC verified", account_id)
+        );
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod kyc_tests {
+    use super::*;
+    use crate::catch_game::PlayerRewardRequest;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    #[test]
+    fn set_kyc_and_expire() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        let erin = ValidAccountId::try_from("erin.near").unwrap();
+        assert!(contract.get_kyc(erin.clone()).is_none());
+
+        contract.set_kyc(erin.clone(), KycStatus::Pending, 1, None);
+        assert!(!contract.is_kyc_verified(erin.clone()));
+
+        contract.set_kyc(erin.clone(), KycStatus::Verified, 2, Some(U64::from(100)));
+        assert_eq!(
+            contract.get_kyc(erin.clone()),
+            Some(KycView {
+                status: KycStatus::Verified,
+                level: 2,
+                expires_at: Some(U64::from(100)),
+                updated_at: U64::from(0),
+                is_verified: true,
+            })
+        );
+
+        at(dex(), 0, 100);
+        assert!(!contract.is_kyc_verified(erin.clone()));
+        assert!(!contract.get_kyc(erin).unwrap().is_verified);
+    }
+
+    #[test]
+    fn revoke_kyc_keeps_balance() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(0));
+        assert_eq!(contract.ft_balance_of(carol()).0, 1000);
+
+        testing_env!(get_context(dex().to_string(), 0));
+        contract.set_kyc(carol(), KycStatus::Revoked, 2, None);
+        assert!(!contract.is_kyc_verified(carol()));
+        assert_eq!(contract.ft_balance_of(carol()).0, 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account carol.near is not KYC verified")]
+    fn transfer_player_reward_unverified() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        seed_reward_pool(&mut contract, 5000);
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(0));
+    }
+
+    #[test]
+    fn transfer_batch_reward_unverified() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(alice()));
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[alice()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        let request = |player_id: ValidAccountId| PlayerRewardRequest {
+            player_id: player_id.into(),
+            obj_ids: vec![0],
+            nonce: U64::from(0),
+        };
+
+        testing_env!(get_context(nft().to_string(), 0));
+        let result = contract.transfer_batch_reward(vec![request(carol()), request(alice())]);
+
+        assert_eq!(result.receipts.len(), 1);
+        assert_eq!(result.receipts[0].player_id, alice().to_string());
+        assert_eq!(
+            result.failed,
+            vec![(0, "Account carol.near is not KYC verified".to_string())]
+        );
+        assert_eq!(contract.ft_balance_of(carol()).0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account carol.near is not KYC verified")]
+    fn transfer_player_reward_revoked() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        seed_reward_pool(&mut contract, 5000);
+        contract.set_kyc(carol(), KycStatus::Revoked, 2, None);
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Account xyzusername.mike.near is not KYC verified")]
+    fn create_user_account_unverified() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.create_user_account(
+            ValidAccountId::try_from("xyzusername").unwrap(),
+            Base58PublicKey::try_from("3tysLvy7KGoE8pznUgXvSHa4vYyGvrDZFcT8jgb8PEQ6").unwrap(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only accounts with KycVerifier role can call this method")]
+    fn set_kyc_not_verifier() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+
+        testing_env!(get_context(carol().to_string(), 0));
+        contract.set_kyc(carol(), KycStatus::Verified, 1, None);
+    }
+}
@@ -0,0 +1,343 @@
+use crate::*;
+
+use near_sdk::collections::TreeMap;
+
+/// Ranks below this are not computed for a single player, counting them would cost gas proportional to the rank
+const MAX_EXACT_RANK: u64 = 100;
+
+/// Score added to a player for an Objective of the given Rarity
+fn rarity_score(rarity: Rarity) -> u64 {
+    match rarity {
+        Rarity::Legendary => 10,
+        Rarity::Rare => 3,
+        Rarity::Common => 1,
+    }
+}
+
+/// Lifetime totals of a player, only Objective rewards are counted and not the tokens received by transfer
+#[derive(BorshDeserialize, BorshSerialize, Default, Clone)]
+pub struct PlayerStats {
+    pub total_rewards: Balance,
+    pub objective_count: u64,
+
+    /// Sum of the Rarity scores of the rewarded Objectives
+    pub score: u64,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct LeaderboardEntry {
+    /// 1 based rank of the player, None if get_player_stats() finds it below the top MAX_EXACT_RANK players
+    pub rank: Option<u64>,
+
+    pub player_id: AccountId,
+    pub total_rewards: U128,
+    pub objective_count: u64,
+    pub score: u64,
+}
+
+/// Player stats along with the players ordered by score
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Ranking {
+    pub player_stats: LookupMap<AccountId, PlayerStats>,
+
+    /// (score, player) in ascending order, so the top players are at the end
+    pub ranking: TreeMap<(u64, AccountId), ()>,
+}
+
+impl Ranking {
+    pub fn new(player_stats_prefix: StorageKey, ranking_prefix: StorageKey) -> Self {
+        Self {
+            player_stats: LookupMap::new(player_stats_prefix.try_to_vec().unwrap()),
+            ranking: TreeMap::new(ranking_prefix.try_to_vec().unwrap()),
+        }
+    }
+
+    pub fn record_reward(&mut self, player_id: &AccountId, reward: Balance, rarity: Rarity) {
+        let mut stats = self.player_stats.get(player_id).unwrap_or_default();
+
+        if stats.objective_count > 0 {
+            self.ranking.remove(&(stats.score, player_id.clone()));
+        }
+
+        stats.total_rewards += reward;
+        stats.objective_count += 1;
+        stats.score += rarity_score(rarity);
+
+        self.ranking.insert(&(stats.score, player_id.clone()), &());
+        self.player_stats.insert(player_id, &stats);
+    }
+
+    pub fn remove_player(&mut self, player_id: &AccountId) {
+        if let Some(stats) = self.player_stats.remove(player_id) {
+            self.ranking.remove(&(stats.score, player_id.clone()));
+        }
+    }
+
+    pub fn top(&self, from_index: u64, limit: u64) -> Vec<LeaderboardEntry> {
+        self.ranking
+            .iter_rev()
+            .enumerate()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .map(|(index, ((_, player_id), ()))| {
+                let stats = self.player_stats.get(&player_id).unwrap();
+
+                LeaderboardEntry {
+                    rank: Some(index as u64 + 1),
+                    player_id,
+                    total_rewards: U128::from(stats.total_rewards),
+                    objective_count: stats.objective_count,
+                    score: stats.score,
+                }
+            })
+            .collect()
+    }
+
+    pub fn player_entry(&self, player_id: AccountId) -> Option<LeaderboardEntry> {
+        let stats = self.player_stats.get(&player_id)?;
+
+        // Players with a higher score, or the same score and a greater account id, are ranked above
+        let key = (stats.score, player_id.clone());
+        let rank = self
+            .ranking
+            .iter_rev()
+            .take(MAX_EXACT_RANK as usize)
+            .position(|(ranked, _)| ranked == key)
+            .map(|index| index as u64 + 1);
+
+        Some(LeaderboardEntry {
+            rank,
+            player_id,
+            total_rewards: U128::from(stats.total_rewards),
+            objective_count: stats.objective_count,
+            score: stats.score,
+        })
+    }
+}
+
+/// Overall and per Season rankings of the players
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Leaderboard {
+    pub overall: Ranking,
+
+    /// Season id -> Ranking of the players in the Season
+    pub seasons: LookupMap<String, Ranking>,
+}
+
+impl Default for Leaderboard {
+    fn default() -> Self {
+        Self {
+            overall: Ranking::new(StorageKey::PlayerStats, StorageKey::Ranking),
+            seasons: LookupMap::new(StorageKey::SeasonRankings.try_to_vec().unwrap()),
+        }
+    }
+}
+
+impl Leaderboard {
+    /// Records the reward of a single Objective, in the Season ranking too if the Objective belongs to a Season
+    pub fn record_reward(
+        &mut self,
+        player_id: &AccountId,
+        reward: Balance,
+        rarity: Rarity,
+        season_id: Option<&String>,
+    ) {
+        self.overall.record_reward(player_id, reward, rarity);
+
+        if let Some(season_id) = season_id {
+            let mut ranking = self.seasons.get(season_id).unwrap_or_else(|| {
+                let season_hash = env::sha256(season_id.as_bytes());
+
+                Ranking::new(
+                    StorageKey::SeasonPlayerStats {
+                        season_hash: season_hash.clone(),
+                    },
+                    StorageKey::SeasonRanking { season_hash },
+                )
+            });
+
+            ranking.record_reward(player_id, reward, rarity);
+            self.seasons.insert(season_id, &ranking);
+        }
+    }
+
+    /// Removes the player from the overall ranking and from the rankings of the Seasons
+    pub fn remove_player(&mut self, player_id: &AccountId, season_ids: Vec<String>) {
+        self.overall.remove_player(player_id);
+
+        for season_id in season_ids {
+            if let Some(mut ranking) = self.seasons.get(&season_id) {
+                ranking.remove_player(player_id);
+                self.seasons.insert(&season_id, &ranking);
+            }
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// View Function - returns paginated view of the players ordered by score, highest first
+    pub fn get_leaderboard(&self, from_index: u64, limit: u64) -> Vec<LeaderboardEntry> {
+        self.leaderboard.overall.top(from_index, limit)
+    }
+
+    /// View Function - returns paginated view of the players ordered by score in the Season, highest first
+    pub fn get_season_leaderboard(
+        &self,
+        season_id: String,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<LeaderboardEntry> {
+        match self.leaderboard.seasons.get(&season_id) {
+            Some(ranking) => ranking.top(from_index, limit),
+            None => vec![],
+        }
+    }
+
+    /// View Function - returns the lifetime reward totals and rank of the player, overall or in the Season
+    ///
+    /// rank is None for players below the top 100, get_leaderboard() pages through all of them
+    pub fn get_player_stats(
+        &self,
+        player_id: AccountId,
+        season_id: Option<String>,
+    ) -> Option<LeaderboardEntry> {
+        match season_id {
+            Some(season_id) => self
+                .leaderboard
+                .seasons
+                .get(&season_id)?
+                .player_entry(player_id),
+            None => self.leaderboard.overall.player_entry(player_id),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod leaderboard_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    fn reward(contract: &mut Contract, player_id: ValidAccountId, obj_ids: Vec<ObjectiveId>) {
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let player_id = player_id.to_string();
+        let nonce = contract.get_reward_nonce(player_id.clone());
+        contract.transfer_player_reward(player_id, obj_ids, nonce);
+    }
+
+    #[test]
+    fn leaderboard() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(alice()));
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[alice(), carol()]);
+        seed_reward_pool(&mut contract, 10000);
+
+        reward(&mut contract, alice(), vec![0]);
+        reward(&mut contract, carol(), vec![0, 1]);
+
+        let leaderboard = contract.get_leaderboard(0, 10);
+        assert_eq!(
+            leaderboard,
+            vec![
+                LeaderboardEntry {
+                    rank: Some(1),
+                    player_id: carol().to_string(),
+                    total_rewards: U128::from(3000),
+                    objective_count: 2,
+                    score: 20,
+                },
+                LeaderboardEntry {
+                    rank: Some(2),
+                    player_id: alice().to_string(),
+                    total_rewards: U128::from(1000),
+                    objective_count: 1,
+                    score: 10,
+                },
+            ]
+        );
+
+        assert_eq!(contract.get_leaderboard(1, 10)[0].rank, Some(2));
+        assert_eq!(
+            contract.get_player_stats(alice().to_string(), None),
+            Some(leaderboard[1].clone())
+        );
+        assert_eq!(contract.get_player_stats(bob().to_string(), None), None);
+    }
+
+    #[test]
+    fn season_leaderboard() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(alice()));
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[alice(), carol()]);
+        seed_reward_pool(&mut contract, 10000);
+
+        contract.set_season("weekend".to_string(), None, None);
+        contract.set_objective_schedule(1, None, None, Some("weekend".to_string()));
+
+        reward(&mut contract, alice(), vec![1]);
+        reward(&mut contract, carol(), vec![0]);
+
+        let season_leaderboard = contract.get_season_leaderboard("weekend".to_string(), 0, 10);
+        assert_eq!(season_leaderboard.len(), 1);
+        assert_eq!(season_leaderboard[0].player_id, alice().to_string());
+        assert_eq!(season_leaderboard[0].total_rewards, U128::from(2000));
+
+        assert!(contract
+            .get_player_stats(carol().to_string(), Some("weekend".to_string()))
+            .is_none());
+        assert_eq!(contract.get_leaderboard(0, 10).len(), 2);
+    }
+
+    #[test]
+    fn player_rank_capped() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        let ranking = &mut contract.leaderboard.overall;
+
+        // Same score for everyone, so the players are ranked by account id
+        for i in 0..=MAX_EXACT_RANK {
+            // Filling the ranking takes more than the gas of a single call
+            testing_env!(get_context(dex().to_string(), 0));
+            ranking.record_reward(&format!("player{:03}.near", i), 1, Rarity::Common);
+        }
+
+        assert_eq!(
+            ranking
+                .player_entry("player100.near".to_string())
+                .unwrap()
+                .rank,
+            Some(1)
+        );
+        assert_eq!(
+            ranking
+                .player_entry("player001.near".to_string())
+                .unwrap()
+                .rank,
+            Some(100)
+        );
+
+        let entry = ranking.player_entry("player000.near".to_string()).unwrap();
+        assert_eq!(entry.rank, None);
+        assert_eq!(entry.score, 1);
+        assert_eq!(
+            contract.get_leaderboard(MAX_EXACT_RANK, 1)[0],
+            LeaderboardEntry {
+                rank: Some(MAX_EXACT_RANK + 1),
+                ..entry
+            }
+        );
+    }
+}
@@ -5,35 +5,107 @@
  *
  * lib.rs is the main entry point.
  * core_impl.rs implements NEP-141 standard
+ * supply.rs implements minting and burning of tokens within an optional max supply
  * storage_impl.rs implements NEP-145 standard for allocating storage per account
+ * allowances.rs implements allowances and delegated transfers paid through the storage deposit
+ * freeze.rs implements compliance freezes of account transfers
  * catch_game.rs implements Objectuve and Reward Functionality for users
+ * rarity.rs implements configurable Rarity Thresholds for Objectives
+ * seasons.rs implements time windows and Seasons for Objectives
+ * quests.rs implements Objective prerequisites and quest lines
+ * categories.rs implements Objective categories, tags and filtered queries
+ * geo.rs implements locations of Objectives on the Catch Map
+ * integrity.rs implements consistency checks of the Objectives and their metadata
+ * reward_curve.rs implements Reward Curves which decide the reward as per the winner_count of an Objective
+ * roles.rs implements Role based access control for administering the contract
+ * pause.rs implements pausing parts of the contract in an emergency
+ * kyc.rs implements the KYC registry which gates player sub-accounts and rewards
+ * treasury.rs implements the reward pool and emission budgets for Objective rewards
+ * leaderboard.rs implements lifetime reward totals and leaderboards of the players
+ * vesting.rs implements vesting of Objective rewards into a locked balance
+ * campaigns.rs implements reward campaigns computed off-chain and claimed with Merkle proofs
+ * vouchers.rs implements reward vouchers signed by the game server and claimed by the players
+ * sponsorships.rs implements Objectives sponsored by businesses through ft_transfer_call and approved by a MetadataManager
+ * migration.rs implements the upgrade of the state of the initial release
  * ft_metadata.rs implements NEP-148 standard for providing token-specific metadata.
  * events.rs extends NEP-297 for better indexing
  * internal.rs contains internal methods for fungible token core.
  **/
+mod allowances;
 mod core_impl;
 mod events;
+mod freeze;
 mod ft_metadata;
 mod internal;
+mod migration;
 mod receiver;
 mod resolver;
 mod storage_impl;
+mod supply;
 mod utils;
 
+mod campaigns;
 mod catch_game;
-
-pub use crate::catch_game::CatchObjectives;
+mod categories;
+mod geo;
+mod integrity;
+mod kyc;
+mod leaderboard;
+mod pause;
+mod quests;
+mod rarity;
+mod reward_curve;
+mod roles;
+mod seasons;
+mod sponsorships;
+mod treasury;
+mod vesting;
+mod vouchers;
+
+pub use crate::allowances::{Allowance, AllowanceView, StorageAccount};
+pub use crate::campaigns::{CampaignId, RewardCampaigns};
+pub use crate::catch_game::{CatchObjectives, ObjectiveId, Rarity, RewardReceipt};
+pub use crate::categories::{LabelIndex, ObjectiveFilter};
 pub use crate::core_impl::{FungibleToken, FungibleTokenCore};
-pub use crate::events::{FtBurnLog, FtMintLog, FtTransferLog};
-pub use crate::ft_metadata::FungibleTokenMetadata;
+pub use crate::events::{
+    AccountFreezeLog, AccountUnfreezeLog, AllowanceRevokeLog, AllowanceUpdateLog, CampaignClaimLog,
+    CampaignCloseLog, CampaignCreateLog, EpochBudgetUpdateLog, FtBurnLog, FtMintLog, FtTransferLog,
+    KycUpdateLog, ObjectiveAddLog, ObjectiveBudgetUpdateLog, ObjectiveLabelsUpdateLog,
+    ObjectiveLocationUpdateLog, ObjectivePrerequisitesUpdateLog, ObjectiveRarityUpdateLog,
+    ObjectiveRetireLog, ObjectiveRewardCurveUpdateLog, ObjectiveScheduleUpdateLog,
+    ObjectiveUpdateLog, ObjectiveVestingUpdateLog, PauseUpdateLog, PlayerRewardLog,
+    RewardBudgetExhaustedLog, RewardPoolFundLog, RewardPoolWithdrawLog, RoleGrantLog,
+    RoleRevokeLog, SeasonUpdateLog, SponsorshipCreateLog, SponsorshipProposeLog,
+    SponsorshipRejectLog, SponsorshipWithdrawLog, VoucherClaimLog, VoucherKeyAddLog,
+    VoucherKeyRemoveLog,
+};
+pub use crate::freeze::{AccountFreeze, FreezeDirection, FrozenAccountView};
+pub use crate::ft_metadata::{FungibleTokenMetadata, FungibleTokenMetadataProvider};
+pub use crate::geo::GeoLocation;
+pub use crate::integrity::IntegrityReport;
+pub use crate::kyc::{KycRecord, KycRegistry, KycStatus, KycView};
+pub use crate::leaderboard::{Leaderboard, LeaderboardEntry};
+pub use crate::pause::{PauseFlag, PauseState};
+pub use crate::quests::QuestLineProgress;
+pub use crate::rarity::{RarityThresholds, ThresholdUnit};
 pub use crate::receiver::ext_fungible_token_receiver;
 pub use crate::resolver::{ext_self, FungibleTokenResolver};
+pub use crate::reward_curve::RewardCurve;
+pub use crate::roles::Role;
+pub use crate::seasons::{ObjectiveStatus, Season};
+pub use crate::sponsorships::{
+    ProposalId, SponsorshipMsg, SponsorshipProposal, SponsorshipProposalView, SponsorshipView,
+    Sponsorships,
+};
 pub use crate::storage_impl::StorageManager;
+pub use crate::treasury::RewardTreasury;
 use crate::utils::is_valid_username;
+pub use crate::vesting::{VestingBalance, VestingGrant, VestingSchedule};
+pub use crate::vouchers::{RewardVoucher, VoucherRegistry};
 
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, Vector};
-use near_sdk::json_types::{Base58PublicKey, Base64VecU8, ValidAccountId, U128};
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet, Vector};
+use near_sdk::json_types::{Base58PublicKey, Base64VecU8, ValidAccountId, U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
     assert_one_yocto, assert_self, env, ext_contract, log, near_bindgen, AccountId, Balance, Gas,
@@ -53,6 +125,37 @@ pub enum StorageKey {
     ObjectiveStats,
     ObjectiveMetadata,     // Lazy Option
     ObjectiveMetadataList, // Vector
+    PlayerObjectives,
+    PlayerObjectiveList { account_hash: Vec<u8> },
+    ObjectiveRarityThresholds,
+    Roles,
+    RoleMembers { role: Role },
+    ObjectiveBudgets,
+    RewardNonces,
+    Seasons,
+    QuestLines,
+    PlayerStats,
+    Ranking,
+    SeasonRankings,
+    SeasonPlayerStats { season_hash: Vec<u8> },
+    SeasonRanking { season_hash: Vec<u8> },
+    Vesting,
+    Campaigns,
+    CampaignClaims,
+    VoucherKeys,
+    VoucherNonces,
+    Categories,
+    Tags,
+    Sponsorships,
+    Regions,
+    Allowances,
+    StorageAccounts,
+    Kyc,
+    FrozenAccounts,
+    SponsorshipProposals,
+    GeoCells,
+    PlayerVoucherNonces { account_hash: Vec<u8> },
+    SponsorOpenProposals,
 }
 
 #[near_bindgen]
@@ -66,56 +169,85 @@ pub struct Contract {
     /// In Game Objectives
     pub catch_objectives: CatchObjectives,
 
-    /// The storage size in bytes for one account.
+    /// The storage size in bytes for one account, including the records kept once it is rewarded as a player
     pub account_storage_usage: StorageUsage,
 
+    /// The most storage in bytes added by rewarding one more Objective to a player, paid from the player's storage deposit
+    pub reward_storage_usage: StorageUsage,
+
     /// Metadata for the Fungible Token
     pub ft_metadata: LazyOption<FungibleTokenMetadata>,
+
+    /// Role -> Accounts having the role
+    pub roles: LookupMap<Role, UnorderedSet<AccountId>>,
+
+    /// Reward pool and emission budgets for Objective rewards
+    pub treasury: RewardTreasury,
+
+    /// Lifetime reward totals and rankings of the players
+    pub leaderboard: Leaderboard,
+
+    /// Reward campaigns claimed with Merkle proofs
+    pub campaigns: RewardCampaigns,
+
+    /// Keys trusted to sign reward vouchers and the claimed voucher nonces
+    pub vouchers: VoucherRegistry,
+
+    /// Escrowed tokens of the sponsored Objectives
+    pub sponsorships: Sponsorships,
+
+    /// Parts of the contract paused by a Pauser
+    pub pause_state: PauseState,
+
+    /// KYC status of the players recorded by a KycVerifier
+    pub kyc: KycRegistry,
 }
 
 #[near_bindgen]
 impl Contract {
     /// Initialize The Contract
+    ///
+    /// max_supply can't be changed later, None allows minting without a cap
     #[init]
     pub fn new(
         owner_id: ValidAccountId,
         total_supply: U128,
         metadata: FungibleTokenMetadata,
+        max_supply: Option<U128>,
     ) -> Self {
         require!(!env::state_exists(), "Already initialized");
         metadata.assert_valid_metadata();
 
+        let max_supply: Option<Balance> = max_supply.map(|max_supply| max_supply.into());
+        require!(
+            max_supply.is_none_or(|max_supply| total_supply.0 <= max_supply),
+            "Total supply can't be greater than the max supply"
+        );
+
         let owner_id: AccountId = owner_id.into();
+        require!(
+            owner_id != env::current_account_id(),
+            "Owner can't be the contract account"
+        );
 
-        let token = FungibleToken {
-            accounts: LookupMap::new(StorageKey::Accounts.try_to_vec().unwrap()),
-            total_supply: total_supply.into(),
-        };
+        let token = FungibleToken::new(
+            LookupMap::new(StorageKey::Accounts.try_to_vec().unwrap()),
+            total_supply.into(),
+            0,
+            max_supply,
+        );
 
         let catch_objectives = CatchObjectives::default();
 
         let ft_metadata =
             LazyOption::new(StorageKey::Metadata.try_to_vec().unwrap(), Some(&metadata));
 
-        let mut this = Self {
-            owner_id: owner_id.clone(),
-            token,
-            catch_objectives,
-            account_storage_usage: 0,
-            ft_metadata,
-        };
-        // Determine cost of insertion into LookupMap
-
-        let initial_storage_usage = env::storage_usage();
-        let tmp_account_id = unsafe { String::from_utf8_unchecked(vec![b'a'; 64]) };
-        this.token.accounts.insert(&tmp_account_id, &0u128);
-        this.account_storage_usage = env::storage_usage() - initial_storage_usage;
-        this.token.accounts.remove(&tmp_account_id);
+        let mut this = Self::internal_init(owner_id.clone(), token, catch_objectives, ft_metadata);
 
         // Make owner have total supply
 
-        let total_supply_u128: u128 = total_supply.into();
-        this.token.accounts.insert(&owner_id, &total_supply_u128);
+        this.token.internal_register_account(&owner_id);
+        this.token.internal_deposit(&owner_id, total_supply.into());
 
         FtMintLog {
             owner_id: owner_id.to_string(),
@@ -124,12 +256,16 @@ impl Contract {
         }
         .emit();
 
+        this.internal_grant_owner_roles(&owner_id);
+
         this
     }
 
     /// Transfer the Fungible Token from one A/c to another A/c
     #[payable]
     pub fn ft_transfer(&mut self, receiver_id: ValidAccountId, amount: U128, memo: Option<String>) {
+        self.assert_not_paused(PauseFlag::Transfers);
+
         self.token
             .ft_transfer(receiver_id.into(), amount.into(), memo)
     }
@@ -144,6 +280,9 @@ impl Contract {
         memo: Option<String>,
         msg: String,
     ) -> PromiseOrValue<U128> {
+        self.assert_not_paused(PauseFlag::Transfers);
+        self.assert_not_paused(PauseFlag::TransferCall);
+
         self.token
             .ft_transfer_call(receiver_id.into(), amount.into(), memo, msg)
     }
@@ -175,13 +314,16 @@ impl Contract {
 
     /// Create Sub Accounts for user and registers them with ft contract, if already registered leaves it unchanged
     ///
+    /// The sub-account has to be KYC verified
+    ///
     /// Also this assumes that there will be enough near for account creation in the contract, this can be ensured and even panic won't cause any issues
     pub fn create_user_account(
         &mut self,
         username: ValidAccountId,
         player_public_key: Base58PublicKey,
     ) {
-        self.assert_owner();
+        self.assert_role(Role::AccountCreator);
+        self.assert_not_paused(PauseFlag::AccountCreation);
 
         let username: String = username.into();
 
@@ -189,8 +331,11 @@ impl Contract {
 
         let subaccount = AccountId::from(format!("{}.{}", username, env::current_account_id()));
 
+        // Player has to be verified under the sub-account id before it is created
+        self.assert_kyc_verified(&subaccount);
+
         if !self.token.accounts.contains_key(&subaccount) {
-            self.token.accounts.insert(&subaccount, &0);
+            self.token.internal_register_account(&subaccount);
         }
 
         Promise::new(subaccount.clone())
@@ -200,17 +345,109 @@ impl Contract {
     }
 }
 
+impl Contract {
+    /// Builds the contract around the token and Objectives, with every other part empty
+    pub(crate) fn internal_init(
+        owner_id: AccountId,
+        token: FungibleToken,
+        catch_objectives: CatchObjectives,
+        ft_metadata: LazyOption<FungibleTokenMetadata>,
+    ) -> Self {
+        let mut this = Self {
+            owner_id,
+            token,
+            catch_objectives,
+            account_storage_usage: 0,
+            reward_storage_usage: 0,
+            ft_metadata,
+            roles: LookupMap::new(StorageKey::Roles.try_to_vec().unwrap()),
+            treasury: RewardTreasury::default(),
+            leaderboard: Leaderboard::default(),
+            campaigns: RewardCampaigns::default(),
+            vouchers: VoucherRegistry::default(),
+            sponsorships: Sponsorships::default(),
+            pause_state: PauseState::default(),
+            kyc: KycRegistry::default(),
+        };
+        // Determine cost of insertion into LookupMap, along with the reward ledger, nonce, stats and storage deposit
+        // of a rewarded player so that the registration deposit pays for them instead of the contract
+
+        let initial_storage_usage = env::storage_usage();
+        let tmp_account_id = unsafe { String::from_utf8_unchecked(vec![b'a'; 64]) };
+        this.token.accounts.insert(&tmp_account_id, &0u128);
+        this.token
+            .storage_accounts
+            .insert(&tmp_account_id, &StorageAccount::default());
+        this.catch_objectives
+            .record_player_objectives(&tmp_account_id, &[]);
+        this.catch_objectives.use_reward_nonce(&tmp_account_id, 0);
+        this.leaderboard
+            .record_reward(&tmp_account_id, 0, Rarity::Common, None);
+        this.account_storage_usage = env::storage_usage() - initial_storage_usage;
+
+        // Every rewarded Objective adds an entry to the reward ledger and at most one to the Season ranking,
+        // the Season ranking itself is created by another player as it is shared by the players of the Season
+
+        let tmp_season_id = unsafe { String::from_utf8_unchecked(vec![b's'; 64]) };
+        let tmp_other_account_id = unsafe { String::from_utf8_unchecked(vec![b'b'; 64]) };
+        this.leaderboard.record_reward(
+            &tmp_other_account_id,
+            0,
+            Rarity::Common,
+            Some(&tmp_season_id),
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        this.catch_objectives
+            .record_player_objectives(&tmp_account_id, &[0]);
+        this.leaderboard
+            .record_reward(&tmp_account_id, 0, Rarity::Common, Some(&tmp_season_id));
+        this.reward_storage_usage = env::storage_usage() - initial_storage_usage;
+
+        this.token.accounts.remove(&tmp_account_id);
+        this.token.storage_accounts.remove(&tmp_account_id);
+        this.catch_objectives.remove_player_records(&tmp_account_id);
+        this.leaderboard
+            .remove_player(&tmp_account_id, vec![tmp_season_id.clone()]);
+        this.leaderboard
+            .remove_player(&tmp_other_account_id, vec![tmp_season_id.clone()]);
+        this.leaderboard.seasons.remove(&tmp_season_id);
+
+        // Contract account holds the reward pool, campaign budgets, locked vesting and sponsorship escrows
+
+        if !this.token.accounts.contains_key(&env::current_account_id()) {
+            this.token
+                .internal_register_account(&env::current_account_id());
+        }
+
+        this
+    }
+
+    /// Owner gets every role except RewardDistributor, which is to be granted to the NFT Contract
+    pub(crate) fn internal_grant_owner_roles(&mut self, owner_id: &AccountId) {
+        for role in Role::all() {
+            if role != Role::RewardDistributor {
+                self.internal_grant_role(role, owner_id);
+
+                RoleGrantLog {
+                    role,
+                    account_id: owner_id.clone(),
+                    sender_id: owner_id.clone(),
+                }
+                .emit();
+            }
+        }
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
 mod ft_core_tests {
     use super::*;
     use utils::test_utils::*;
 
+    use near_sdk::testing_env;
     use near_sdk::MockedBlockchain;
-    use near_sdk::{testing_env, Balance};
-
-    const ONE_YOCTO: Balance = 1;
-    const STORAGE_COST: Balance = 1_250_000_000_000_000_000_000; // 1 Near = 10^24 Yocto Near
 
     // Test for new()
 
@@ -222,12 +459,9 @@ mod ft_core_tests {
 
         assert_eq!(contract.ft_total_supply().0, 1_000_000_000_000_000);
         assert_eq!(contract.ft_balance_of(alice()).0, 0);
-        assert_eq!(contract.ft_balance_of(bob().into()).0, 0);
-        assert_eq!(contract.ft_balance_of(carol().into()).0, 0);
-        assert_eq!(
-            contract.ft_balance_of(dex().into()).0,
-            1_000_000_000_000_000
-        );
+        assert_eq!(contract.ft_balance_of(bob()).0, 0);
+        assert_eq!(contract.ft_balance_of(carol()).0, 0);
+        assert_eq!(contract.ft_balance_of(dex()).0, 1_000_000_000_000_000);
     }
 
     #[test]
@@ -272,6 +506,12 @@ mod ft_core_tests {
             Base58PublicKey::try_from("3tysLvy7KGoE8pznUgXvSHa4vYyGvrDZFcT8jgb8PEQ6").unwrap();
 
         let mut contract = create_contract();
+        contract.set_kyc(
+            ValidAccountId::try_from("xyzusername.mike.near").unwrap(),
+            KycStatus::Verified,
+            1,
+            None,
+        );
         contract.create_user_account(username, player_public_key);
     }
 }
@@ -0,0 +1,198 @@
+use crate::*;
+
+use crate::catch_game::{Objective, ObjectiveMetadata, ObjectiveStats};
+
+/// Objective as stored by the initial release, before the schedules, curves, vesting and labels
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldObjective {
+    pub title: String,
+    pub reward: Balance,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldFungibleToken {
+    pub accounts: LookupMap<AccountId, Balance>,
+    pub total_supply: Balance,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldCatchObjectives {
+    pub objectives: Vector<OldObjective>,
+    pub obj_metadata: LazyOption<Vector<ObjectiveMetadata>>,
+    pub stats: Vector<ObjectiveStats>,
+}
+
+/// Contract state of the initial release, the storage keys of its collections are unchanged
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldContract {
+    pub owner_id: AccountId,
+    pub token: OldFungibleToken,
+    pub catch_objectives: OldCatchObjectives,
+    pub account_storage_usage: StorageUsage,
+    pub ft_metadata: LazyOption<FungibleTokenMetadata>,
+}
+
+impl From<OldObjective> for Objective {
+    fn from(objective: OldObjective) -> Self {
+        Objective {
+            title: objective.title,
+            reward: objective.reward,
+            retired: false,
+            starts_at: None,
+            ends_at: None,
+            season_id: None,
+            prerequisites: vec![],
+            quest_line: None,
+            reward_curve: RewardCurve::Flat,
+            vesting: None,
+            category: None,
+            tags: vec![],
+            location: None,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Contract Account Only - Upgrade the state of the initial release after deploying this code
+    ///
+    /// The initial release didn't count the registered accounts, registered_accounts has to be taken from an indexer
+    /// as the Rarity of the Objectives depends on it. max_supply can't be changed later, None allows minting without a cap
+    #[init(ignore_state)]
+    pub fn migrate(registered_accounts: u64, max_supply: Option<U128>) -> Self {
+        assert_self();
+
+        let old: OldContract = env::state_read().expect("Old state doesn't exist");
+
+        let max_supply: Option<Balance> = max_supply.map(|max_supply| max_supply.into());
+        require!(
+            max_supply.is_none_or(|max_supply| old.token.total_supply <= max_supply),
+            "Total supply can't be greater than the max supply"
+        );
+
+        // Objectives keep their storage key and length, each one is rewritten in the new layout
+
+        let mut old_objectives = old.catch_objectives.objectives;
+        for index in 0..old_objectives.len() {
+            let objective: Objective = old_objectives.get(index).unwrap().into();
+            old_objectives.replace_raw(index, &objective.try_to_vec().unwrap());
+        }
+        let objectives: Vector<Objective> =
+            Vector::try_from_slice(&old_objectives.try_to_vec().unwrap()).unwrap();
+
+        let catch_objectives = CatchObjectives::new(
+            objectives,
+            old.catch_objectives.obj_metadata,
+            old.catch_objectives.stats,
+        );
+
+        let token = FungibleToken::new(
+            old.token.accounts,
+            old.token.total_supply,
+            registered_accounts,
+            max_supply,
+        );
+
+        let owner_id = old.owner_id;
+        let mut this =
+            Self::internal_init(owner_id.clone(), token, catch_objectives, old.ft_metadata);
+        this.internal_grant_owner_roles(&owner_id);
+
+        this
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod migration_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    fn old_metadata() -> FungibleTokenMetadata {
+        FungibleTokenMetadata {
+            spec: String::from("1.1.0"),
+            name: String::from("CAT Token"),
+            symbol: String::from("CAT"),
+            icon: String::from("C-A-T-C-H"),
+            reference: String::from("ipfs://token"),
+            reference_hash: Base64VecU8::from([5_u8; 32].to_vec()),
+            decimals: 0,
+        }
+    }
+
+    fn write_old_state() {
+        let mut accounts = LookupMap::new(StorageKey::Accounts.try_to_vec().unwrap());
+        accounts.insert(&dex().to_string(), &900);
+        accounts.insert(&alice().to_string(), &100);
+
+        let mut objectives = Vector::new(StorageKey::Objective.try_to_vec().unwrap());
+        for objective in Objective::default_list() {
+            objectives.push(&OldObjective {
+                title: objective.title,
+                reward: objective.reward,
+            });
+        }
+
+        let mut obj_metadata_list =
+            Vector::new(StorageKey::ObjectiveMetadataList.try_to_vec().unwrap());
+        obj_metadata_list.extend(ObjectiveMetadata::default_list());
+
+        let mut stats = Vector::new(StorageKey::ObjectiveStats.try_to_vec().unwrap());
+        stats.extend(ObjectiveStats::default_list());
+
+        env::state_write(&OldContract {
+            owner_id: dex().to_string(),
+            token: OldFungibleToken {
+                accounts,
+                total_supply: 1000,
+            },
+            catch_objectives: OldCatchObjectives {
+                objectives,
+                obj_metadata: LazyOption::new(
+                    StorageKey::ObjectiveMetadata.try_to_vec().unwrap(),
+                    Some(&obj_metadata_list),
+                ),
+                stats,
+            },
+            account_storage_usage: 0,
+            ft_metadata: LazyOption::new(
+                StorageKey::Metadata.try_to_vec().unwrap(),
+                Some(&old_metadata()),
+            ),
+        });
+    }
+
+    #[test]
+    fn migrate_from_initial_release() {
+        testing_env!(get_context(mike().to_string(), 0));
+        write_old_state();
+
+        let contract = Contract::migrate(2, None);
+
+        assert_eq!(contract.ft_total_supply(), U128(1000));
+        assert_eq!(contract.ft_balance_of(dex()), U128(900));
+        assert_eq!(contract.ft_balance_of(alice()), U128(100));
+        assert_eq!(contract.token.registered_accounts, 3);
+        assert!(contract.token.accounts.contains_key(&mike().to_string()));
+        assert!(contract.account_storage_usage > 0);
+        assert_eq!(contract.ft_metadata().symbol, "CAT");
+        assert!(contract.has_role(Role::Admin, dex()));
+
+        let objectives = contract.get_objectives(0, 2, None);
+        assert_eq!(objectives.len(), 2);
+        assert_eq!(objectives[0].objective.title, "Objective1");
+        assert_eq!(objectives[1].objective.reward, 2000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Method is private")]
+    fn migrate_by_others() {
+        testing_env!(get_context(dex().to_string(), 0));
+        write_old_state();
+
+        Contract::migrate(2, None);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::*;
+
+/// Parts of the contract which can be paused independently
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum PauseFlag {
+    /// ft_transfer, ft_transfer_from and ft_burn, ft_transfer_call too
+    ///
+    /// Also funding and withdrawing the reward pool, withdrawing and refunding sponsorships
+    Transfers,
+
+    /// ft_transfer_call, which also funds sponsored Objectives
+    TransferCall,
+
+    /// Objective rewards, reward vouchers, campaign claims and claiming vested rewards
+    Rewards,
+
+    /// storage_deposit of a new account and storage_unregister, top-ups and storage_withdraw are never paused
+    Registrations,
+
+    /// create_user_account
+    AccountCreation,
+}
+
+impl PauseFlag {
+    pub fn all() -> Vec<PauseFlag> {
+        vec![
+            PauseFlag::Transfers,
+            PauseFlag::TransferCall,
+            PauseFlag::Rewards,
+            PauseFlag::Registrations,
+            PauseFlag::AccountCreation,
+        ]
+    }
+}
+
+/// Paused parts of the contract, ft_resolve_transfer() is never paused so that pending refunds always go through
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Default, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseState {
+    pub transfers: bool,
+    pub transfer_call: bool,
+    pub rewards: bool,
+    pub registrations: bool,
+    pub account_creation: bool,
+}
+
+impl PauseState {
+    fn flag_mut(&mut self, flag: PauseFlag) -> &mut bool {
+        match flag {
+            PauseFlag::Transfers => &mut self.transfers,
+            PauseFlag::TransferCall => &mut self.transfer_call,
+            PauseFlag::Rewards => &mut self.rewards,
+            PauseFlag::Registrations => &mut self.registrations,
+            PauseFlag::AccountCreation => &mut self.account_creation,
+        }
+    }
+
+    pub fn is_paused(&self, flag: PauseFlag) -> bool {
+        match flag {
+            PauseFlag::Transfers => self.transfers,
+            PauseFlag::TransferCall => self.transfer_call,
+            PauseFlag::Rewards => self.rewards,
+            PauseFlag::Registrations => self.registrations,
+            PauseFlag::AccountCreation => self.account_creation,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Pauser Only - Pause the given parts of the contract, the reason is logged along with the event
+    pub fn pause(&mut self, flags: Vec<PauseFlag>, reason: String) {
+        self.internal_set_paused(flags, reason, true);
+    }
+
+    /// Pauser Only - Unpause the given parts of the contract, the reason is logged along with the event
+    pub fn unpause(&mut self, flags: Vec<PauseFlag>, reason: String) {
+        self.internal_set_paused(flags, reason, false);
+    }
+
+    /// View Function - returns which parts of the contract are paused
+    pub fn get_pause_state(&self) -> PauseState {
+        self.pause_state.clone()
+    }
+
+    /// View Function - returns true if the given part of the contract is paused
+    pub fn is_paused(&self, flag: PauseFlag) -> bool {
+        self.pause_state.is_paused(flag)
+    }
+}
+
+impl Contract {
+    pub fn assert_not_paused(&self, flag: PauseFlag) {
+        require!(
+            !self.pause_state.is_paused(flag),
+            format!("{:?} are paused", flag)
+        );
+    }
+
+    fn internal_set_paused(&mut self, flags: Vec<PauseFlag>, reason: String, paused: bool) {
+        self.assert_role(Role::Pauser);
+        require!(!flags.is_empty(), "At least one flag has to be passed");
+
+        for flag in flags.iter() {
+            *self.pause_state.flag_mut(*flag) = paused;
+        }
+
+        PauseUpdateLog {
+            flags,
+            paused,
+            reason,
+            sender_id: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod pause_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    #[test]
+    fn pause_and_unpause() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+
+        contract.pause(PauseFlag::all(), "Bug in ft_resolve_transfer".to_string());
+        assert!(PauseFlag::all()
+            .into_iter()
+            .all(|flag| contract.is_paused(flag)));
+
+        contract.unpause(vec![PauseFlag::Transfers], "Fixed".to_string());
+        assert_eq!(
+            contract.get_pause_state(),
+            PauseState {
+                transfers: false,
+                transfer_call: true,
+                rewards: true,
+                registrations: true,
+                account_creation: true,
+            }
+        );
+
+        testing_env!(get_context(dex().to_string(), ONE_YOCTO));
+        contract.ft_transfer(carol(), U128::from(1000), None);
+        assert_eq!(contract.ft_balance_of(carol()).0, 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Transfers are paused")]
+    fn ft_transfer_paused() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        contract.pause(vec![PauseFlag::Transfers], "Incident".to_string());
+
+        testing_env!(get_context(dex().to_string(), ONE_YOCTO));
+        contract.ft_transfer(carol(), U128::from(1000), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Rewards are paused")]
+    fn transfer_player_reward_paused() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        seed_reward_pool(&mut contract, 5000);
+        contract.pause(
+            vec![PauseFlag::Rewards],
+            "NFT Contract compromised".to_string(),
+        );
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Registrations are paused")]
+    fn storage_deposit_paused() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.pause(vec![PauseFlag::Registrations], "Incident".to_string());
+        contract.storage_deposit(Some(carol()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only accounts with Pauser role can call this method")]
+    fn pause_not_pauser() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+
+        testing_env!(get_context(carol().to_string(), 0));
+        contract.pause(vec![PauseFlag::Transfers], "Incident".to_string());
+    }
+
+    #[test]
+    fn storage_top_up_not_paused() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        contract.pause(vec![PauseFlag::Registrations], "Incident".to_string());
+
+        testing_env!(get_context(carol().to_string(), 1000));
+        contract.storage_deposit(None);
+
+        testing_env!(get_context(carol().to_string(), ONE_YOCTO));
+        contract.storage_withdraw(None);
+        assert_eq!(contract.storage_balance_of(carol()).unwrap().available.0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Registrations are paused")]
+    fn storage_unregister_paused() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        contract.pause(vec![PauseFlag::Registrations], "Incident".to_string());
+
+        testing_env!(get_context(carol().to_string(), ONE_YOCTO));
+        contract.storage_unregister(None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Transfers are paused")]
+    fn fund_reward_pool_paused() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.pause(vec![PauseFlag::Transfers], "Incident".to_string());
+
+        testing_env!(get_context(dex().to_string(), ONE_YOCTO));
+        contract.fund_reward_pool(U128::from(1000));
+    }
+
+    #[test]
+    #[should_panic(expected = "Transfers are paused")]
+    fn withdraw_reward_pool_paused() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        seed_reward_pool(&mut contract, 5000);
+        contract.pause(vec![PauseFlag::Transfers], "Incident".to_string());
+
+        contract.withdraw_reward_pool(dex(), U128::from(1000));
+    }
+}
@@ -0,0 +1,290 @@
+use crate::*;
+
+use crate::catch_game::Objective;
+
+/// Max number of prerequisites an Objective can have, keeps the reward call gas bounded
+const MAX_PREREQUISITES: usize = 8;
+
+/// Progress of a player through a quest line
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct QuestLineProgress {
+    pub quest_line: String,
+
+    /// Every Objective of the quest line, in the order they were added to it
+    pub obj_ids: Vec<ObjectiveId>,
+
+    /// Objectives of the quest line already rewarded to the player
+    pub completed: Vec<ObjectiveId>,
+
+    /// Active Objectives of the quest line which the player can be rewarded for right now
+    pub next: Vec<ObjectiveId>,
+}
+
+impl CatchObjectives {
+    /// Prerequisites have to be existing Objectives added before this one, so they can never form a cycle
+    pub fn assert_valid_prerequisites(&self, obj_id: ObjectiveId, objective: &Objective) {
+        require!(
+            objective.prerequisites.len() <= MAX_PREREQUISITES,
+            format!(
+                "An Objective can have at most {} prerequisites",
+                MAX_PREREQUISITES
+            )
+        );
+
+        for (i, prerequisite) in objective.prerequisites.iter().enumerate() {
+            require!(
+                *prerequisite < obj_id,
+                "Prerequisites have to be added before the Objective"
+            );
+            require!(
+                !objective.prerequisites[..i].contains(prerequisite),
+                "Duplicate prerequisite"
+            );
+        }
+
+        if let Some(quest_line) = &objective.quest_line {
+            require!(!quest_line.is_empty(), "Quest line can't be empty");
+        }
+    }
+
+    /// Moves the Objective from its old quest line to the new one
+    pub fn index_quest_line(
+        &mut self,
+        obj_id: ObjectiveId,
+        old_quest_line: Option<&String>,
+        new_quest_line: Option<&String>,
+    ) {
+        if old_quest_line == new_quest_line {
+            return;
+        }
+
+        if let Some(old_quest_line) = old_quest_line {
+            let mut obj_ids = self.quest_lines.get(old_quest_line).unwrap_or_default();
+            obj_ids.retain(|id| *id != obj_id);
+
+            if obj_ids.is_empty() {
+                self.quest_lines.remove(old_quest_line);
+            } else {
+                self.quest_lines.insert(old_quest_line, &obj_ids);
+            }
+        }
+
+        if let Some(new_quest_line) = new_quest_line {
+            let mut obj_ids = self.quest_lines.get(new_quest_line).unwrap_or_default();
+            obj_ids.push(obj_id);
+            self.quest_lines.insert(new_quest_line, &obj_ids);
+        }
+    }
+
+    /// Returns true if every prerequisite of the Objective is rewarded to the player
+    pub fn prerequisites_met(&self, player_id: &AccountId, objective: &Objective) -> bool {
+        if objective.prerequisites.is_empty() {
+            return true;
+        }
+
+        match self.player_objectives.get(player_id) {
+            Some(rewarded) => objective
+                .prerequisites
+                .iter()
+                .all(|prerequisite| rewarded.get(prerequisite).is_some()),
+            None => false,
+        }
+    }
+
+    fn quest_line_progress(
+        &self,
+        player_id: &AccountId,
+        quest_line: String,
+        obj_ids: Vec<ObjectiveId>,
+    ) -> QuestLineProgress {
+        let rewarded = self.player_objectives.get(player_id);
+        let is_rewarded = |obj_id: &ObjectiveId| {
+            rewarded
+                .as_ref()
+                .is_some_and(|rewarded| rewarded.get(obj_id).is_some())
+        };
+
+        let completed: Vec<ObjectiveId> = obj_ids.iter().copied().filter(is_rewarded).collect();
+
+        let next = obj_ids
+            .iter()
+            .copied()
+            .filter(|obj_id| !is_rewarded(obj_id))
+            .filter(|obj_id| {
+                let objective = self.get_objective(*obj_id);
+
+                self.objective_status(&objective) == ObjectiveStatus::Active
+                    && objective.prerequisites.iter().all(is_rewarded)
+            })
+            .collect();
+
+        QuestLineProgress {
+            quest_line,
+            obj_ids,
+            completed,
+            next,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// MetadataManager Only - Set the prerequisites and the quest line of an Objective
+    ///
+    /// Prerequisites have to be Objectives added before this one
+    pub fn set_objective_prerequisites(
+        &mut self,
+        obj_id: ObjectiveId,
+        prerequisites: Vec<ObjectiveId>,
+        quest_line: Option<String>,
+    ) {
+        self.assert_role(Role::MetadataManager);
+
+        let mut objective = self.catch_objectives.get_objective(obj_id);
+        let old_quest_line = std::mem::replace(&mut objective.quest_line, quest_line);
+        objective.prerequisites = prerequisites;
+
+        self.catch_objectives
+            .assert_valid_prerequisites(obj_id, &objective);
+        self.catch_objectives.index_quest_line(
+            obj_id,
+            old_quest_line.as_ref(),
+            objective.quest_line.as_ref(),
+        );
+        self.catch_objectives
+            .objectives
+            .replace(obj_id.into(), &objective);
+
+        ObjectivePrerequisitesUpdateLog {
+            obj_id,
+            prerequisites: objective.prerequisites,
+            quest_line: objective.quest_line,
+        }
+        .emit();
+    }
+
+    /// View Function - returns the Objectives of the quest line
+    pub fn get_quest_line(&self, quest_line: String) -> Vec<ObjectiveId> {
+        self.catch_objectives
+            .quest_lines
+            .get(&quest_line)
+            .unwrap_or_default()
+    }
+
+    /// View Function - returns the progress of the player through each quest line along with the next unlockable Objectives
+    pub fn get_player_quest_progress(
+        &self,
+        player_id: AccountId,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<QuestLineProgress> {
+        self.catch_objectives
+            .quest_lines
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .map(|(quest_line, obj_ids)| {
+                self.catch_objectives
+                    .quest_line_progress(&player_id, quest_line, obj_ids)
+            })
+            .collect()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod quests_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    /// Objective1 -> Objective2 quest line, with carol registered and the reward pool funded
+    fn create_quest_contract() -> Contract {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        contract.set_objective_prerequisites(0, vec![], Some("tutorial".to_string()));
+        contract.set_objective_prerequisites(1, vec![0], Some("tutorial".to_string()));
+
+        contract
+    }
+
+    #[test]
+    #[should_panic(expected = "Prerequisites have to be added before the Objective")]
+    fn set_prerequisites_invalid() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.set_objective_prerequisites(0, vec![1], None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Prerequisites of the Objective are not rewarded to the player")]
+    fn transfer_reward_prerequisites_not_met() {
+        let mut contract = create_quest_contract();
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(carol().to_string(), vec![1], U64::from(0));
+    }
+
+    #[test]
+    fn transfer_reward_prerequisites_met() {
+        let mut contract = create_quest_contract();
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(0));
+        contract.transfer_player_reward(carol().to_string(), vec![1], U64::from(1));
+
+        assert_eq!(contract.ft_balance_of(carol()).0, 3000);
+    }
+
+    #[test]
+    fn transfer_reward_prerequisites_in_same_request() {
+        let mut contract = create_quest_contract();
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(carol().to_string(), vec![1, 0], U64::from(0));
+
+        assert_eq!(contract.ft_balance_of(carol()).0, 3000);
+    }
+
+    #[test]
+    fn get_player_quest_progress() {
+        let mut contract = create_quest_contract();
+        let player = carol().to_string();
+
+        assert_eq!(contract.get_quest_line("tutorial".to_string()), vec![0, 1]);
+        assert_eq!(
+            contract.get_player_quest_progress(player.clone(), 0, 10),
+            vec![QuestLineProgress {
+                quest_line: "tutorial".to_string(),
+                obj_ids: vec![0, 1],
+                completed: vec![],
+                next: vec![0],
+            }]
+        );
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(player.clone(), vec![0], U64::from(0));
+
+        let progress = contract.get_player_quest_progress(player, 0, 10);
+        assert_eq!(progress[0].completed, vec![0]);
+        assert_eq!(progress[0].next, vec![1]);
+    }
+
+    #[test]
+    fn move_objective_to_another_quest_line() {
+        let mut contract = create_quest_contract();
+
+        contract.set_objective_prerequisites(0, vec![], None);
+
+        assert_eq!(contract.get_quest_line("tutorial".to_string()), vec![1]);
+    }
+}
@@ -0,0 +1,285 @@
+use crate::*;
+
+/// Unit in which Rarity Thresholds are expressed
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ThresholdUnit {
+    /// Absolute number of winners
+    Count,
+
+    /// Basis points (1/100th of a percent) of the accounts registered with the FT contract
+    BasisPoints,
+}
+
+/// Bands used to decide the Rarity of an Objective from its winner_count
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RarityThresholds {
+    /// Objective is Legendary as long as winner_count <= legendary_max
+    pub legendary_max: U128,
+
+    /// Objective is Rare as long as winner_count <= rare_max, Common after that
+    pub rare_max: U128,
+
+    pub unit: ThresholdUnit,
+}
+
+impl Default for RarityThresholds {
+    fn default() -> Self {
+        Self {
+            legendary_max: U128::from(1000),
+            rare_max: U128::from(20000),
+            unit: ThresholdUnit::Count,
+        }
+    }
+}
+
+impl RarityThresholds {
+    pub fn assert_valid_thresholds(&self) {
+        require!(
+            self.legendary_max.0 <= self.rare_max.0,
+            "legendary_max can't be greater than rare_max"
+        );
+
+        if self.unit == ThresholdUnit::BasisPoints {
+            require!(
+                self.rare_max.0 <= 10_000,
+                "Basis points can't be greater than 10000"
+            );
+        }
+    }
+
+    pub fn rarity_of(&self, winner_count: u128, registered_accounts: u64) -> Rarity {
+        let (legendary_max, rare_max) = match self.unit {
+            ThresholdUnit::Count => (self.legendary_max.0, self.rare_max.0),
+            ThresholdUnit::BasisPoints => (
+                self.legendary_max.0 * u128::from(registered_accounts) / 10_000,
+                self.rare_max.0 * u128::from(registered_accounts) / 10_000,
+            ),
+        };
+
+        if winner_count <= legendary_max {
+            Rarity::Legendary
+        } else if winner_count <= rare_max {
+            Rarity::Rare
+        } else {
+            Rarity::Common
+        }
+    }
+}
+
+impl CatchObjectives {
+    /// Returns the Rarity Thresholds applicable to the Objective, the Objective specific ones if set else the global ones
+    pub fn get_rarity_thresholds(&self, obj_id: ObjectiveId) -> RarityThresholds {
+        self.obj_rarity_thresholds
+            .get(&obj_id)
+            .unwrap_or_else(|| self.rarity_thresholds.clone())
+    }
+
+    /// Recomputes the Rarity of the Objective, returns the old and new Rarity if it has changed
+    pub fn refresh_rarity(
+        &mut self,
+        obj_id: ObjectiveId,
+        registered_accounts: u64,
+    ) -> Option<(Rarity, Rarity)> {
+        let mut obj_stat = self
+            .stats
+            .get(obj_id.into())
+            .unwrap_or_else(|| env::panic(b"Invariant : Invalid Objective"));
+
+        let new_rarity = self
+            .get_rarity_thresholds(obj_id)
+            .rarity_of(obj_stat.winner_count, registered_accounts);
+
+        if new_rarity == obj_stat.rarity {
+            return None;
+        }
+
+        let old_rarity = std::mem::replace(&mut obj_stat.rarity, new_rarity);
+        self.stats.replace(obj_id.into(), &obj_stat);
+
+        Some((old_rarity, new_rarity))
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// MetadataManager Only - Set the global Rarity Thresholds
+    ///
+    /// Rarity of an Objective is recomputed when it is rewarded, refresh_rarities() recomputes the rest page by page
+    pub fn set_rarity_thresholds(&mut self, thresholds: RarityThresholds) {
+        self.assert_role(Role::MetadataManager);
+        thresholds.assert_valid_thresholds();
+
+        self.catch_objectives.rarity_thresholds = thresholds;
+    }
+
+    /// MetadataManager Only - Override the Rarity Thresholds of a single Objective, None removes the override
+    ///
+    /// Rarity of the Objective is recomputed
+    pub fn set_objective_rarity_thresholds(
+        &mut self,
+        obj_id: ObjectiveId,
+        thresholds: Option<RarityThresholds>,
+    ) {
+        self.assert_role(Role::MetadataManager);
+        self.catch_objectives.get_objective(obj_id);
+
+        match thresholds {
+            Some(thresholds) => {
+                thresholds.assert_valid_thresholds();
+                self.catch_objectives
+                    .obj_rarity_thresholds
+                    .insert(&obj_id, &thresholds);
+            }
+            None => {
+                self.catch_objectives.obj_rarity_thresholds.remove(&obj_id);
+            }
+        }
+
+        self.internal_refresh_rarity(obj_id);
+    }
+
+    /// MetadataManager Only - Recompute Rarity of limit Objectives starting at from_index
+    ///
+    /// returns from_index of the next page, it is the Objective count once every Objective is refreshed
+    pub fn refresh_rarities(&mut self, from_index: u64, limit: u64) -> u64 {
+        self.assert_role(Role::MetadataManager);
+
+        let end_index = std::cmp::min(
+            from_index.saturating_add(limit),
+            self.catch_objectives.stats.len(),
+        );
+
+        for obj_id in from_index..end_index {
+            self.internal_refresh_rarity(obj_id as ObjectiveId);
+        }
+
+        end_index
+    }
+
+    /// View Function - returns the global Rarity Thresholds
+    pub fn get_rarity_thresholds(&self) -> RarityThresholds {
+        self.catch_objectives.rarity_thresholds.clone()
+    }
+
+    /// View Function - returns the Rarity Thresholds applicable to the Objective
+    pub fn get_objective_rarity_thresholds(&self, obj_id: ObjectiveId) -> RarityThresholds {
+        self.catch_objectives.get_objective(obj_id);
+        self.catch_objectives.get_rarity_thresholds(obj_id)
+    }
+}
+
+impl Contract {
+    fn internal_refresh_rarity(&mut self, obj_id: ObjectiveId) {
+        if let Some((old_rarity, new_rarity)) = self
+            .catch_objectives
+            .refresh_rarity(obj_id, self.token.registered_accounts)
+        {
+            ObjectiveRarityUpdateLog {
+                obj_id,
+                old_rarity,
+                new_rarity,
+            }
+            .emit();
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod rarity_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    fn thresholds(legendary_max: u128, rare_max: u128, unit: ThresholdUnit) -> RarityThresholds {
+        RarityThresholds {
+            legendary_max: U128::from(legendary_max),
+            rare_max: U128::from(rare_max),
+            unit,
+        }
+    }
+
+    #[test]
+    fn rarity_of() {
+        let count = thresholds(1, 2, ThresholdUnit::Count);
+        assert_eq!(count.rarity_of(1, 0), Rarity::Legendary);
+        assert_eq!(count.rarity_of(2, 0), Rarity::Rare);
+        assert_eq!(count.rarity_of(3, 0), Rarity::Common);
+
+        // 10% Legendary, 50% Rare
+        let basis_points = thresholds(1000, 5000, ThresholdUnit::BasisPoints);
+        assert_eq!(basis_points.rarity_of(10, 100), Rarity::Legendary);
+        assert_eq!(basis_points.rarity_of(11, 100), Rarity::Rare);
+        assert_eq!(basis_points.rarity_of(51, 100), Rarity::Common);
+    }
+
+    #[test]
+    #[should_panic(expected = "legendary_max can't be greater than rare_max")]
+    fn set_rarity_thresholds_invalid() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.set_rarity_thresholds(thresholds(2, 1, ThresholdUnit::Count));
+    }
+
+    #[test]
+    fn set_rarity_thresholds() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(0));
+
+        testing_env!(get_context(dex().to_string(), 0));
+        contract.set_rarity_thresholds(thresholds(0, 1, ThresholdUnit::Count));
+        assert_eq!(
+            contract.get_objectives(0, 1, None)[0].stats.rarity,
+            Rarity::Legendary
+        );
+
+        assert_eq!(contract.refresh_rarities(0, 1), 1);
+        assert_eq!(contract.refresh_rarities(1, 10), 2);
+
+        let json_objs = contract.get_objectives(0, 2, None);
+        assert_eq!(json_objs[0].stats.rarity, Rarity::Rare);
+        assert_eq!(json_objs[1].stats.rarity, Rarity::Legendary);
+
+        assert_eq!(
+            near_sdk::test_utils::get_logs(),
+            vec![
+                r#"EVENT_JSON:{"standard":"catch","version":"1.0.0","event":"objective_rarity_update","data":{"obj_id":0,"old_rarity":"Legendary","new_rarity":"Rare"}}"#
+            ]
+        );
+    }
+
+    #[test]
+    fn set_objective_rarity_thresholds() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        let obj_thresholds = thresholds(0, 0, ThresholdUnit::BasisPoints);
+
+        contract.set_objective_rarity_thresholds(1, Some(obj_thresholds.clone()));
+        assert_eq!(contract.get_objective_rarity_thresholds(1), obj_thresholds);
+        assert_eq!(
+            contract.get_objective_rarity_thresholds(0),
+            RarityThresholds::default()
+        );
+
+        contract.set_objective_rarity_thresholds(1, None);
+        assert_eq!(
+            contract.get_objective_rarity_thresholds(1),
+            RarityThresholds::default()
+        );
+    }
+}
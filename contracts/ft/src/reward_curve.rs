@@ -0,0 +1,298 @@
+use crate::*;
+
+use crate::catch_game::{Objective, ObjectiveStats};
+use crate::utils::mul_div;
+
+/// Decides how the reward of an Objective changes as more players achieve it
+///
+/// The curve is evaluated at claim time with the winner_count and Rarity before the claim,
+/// so the first winner always gets the full reward of a decaying curve
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, PartialEq, Default,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RewardCurve {
+    /// Every winner gets the Objective reward
+    #[default]
+    Flat,
+
+    /// Reward decreases by decrement for every winner, never going below floor
+    Linear { decrement: U128, floor: U128 },
+
+    /// Reward depends on the Rarity of the Objective at claim time
+    Step {
+        legendary: U128,
+        rare: U128,
+        common: U128,
+    },
+
+    /// Reward halves every half_life winners, never going below floor
+    Exponential { half_life: U128, floor: U128 },
+}
+
+impl RewardCurve {
+    pub fn assert_valid_curve(&self, reward: Balance) {
+        match self {
+            RewardCurve::Flat | RewardCurve::Step { .. } => {}
+            RewardCurve::Linear { floor, .. } => {
+                require!(floor.0 <= reward, "Floor can't be greater than the reward");
+            }
+            RewardCurve::Exponential { half_life, floor } => {
+                require!(half_life.0 > 0, "Half life should be positive");
+                require!(floor.0 <= reward, "Floor can't be greater than the reward");
+            }
+        }
+    }
+
+    /// Reward for the next winner of an Objective with the given base reward and stats
+    pub fn reward_at(&self, reward: Balance, stats: &ObjectiveStats) -> Balance {
+        let winner_count = stats.winner_count;
+
+        match self {
+            RewardCurve::Flat => reward,
+            RewardCurve::Linear { decrement, floor } => {
+                let decay = decrement.0.saturating_mul(winner_count);
+                std::cmp::max(reward.saturating_sub(decay), floor.0)
+            }
+            RewardCurve::Step {
+                legendary,
+                rare,
+                common,
+            } => match stats.rarity {
+                Rarity::Legendary => legendary.0,
+                Rarity::Rare => rare.0,
+                Rarity::Common => common.0,
+            },
+            RewardCurve::Exponential { half_life, floor } => {
+                let halvings = winner_count / half_life.0;
+
+                // Linear interpolation between two halvings keeps the curve smooth
+                // Shifting by 128 or more overflows, the reward is fully decayed by then
+                let halve = |times: u128| {
+                    u32::try_from(times)
+                        .ok()
+                        .and_then(|times| reward.checked_shr(times))
+                        .unwrap_or(0)
+                };
+                let (start, end) = (halve(halvings), halve(halvings.saturating_add(1)));
+                let progress = winner_count % half_life.0;
+                let decayed = start - mul_div(start - end, progress, half_life.0);
+
+                std::cmp::max(decayed, floor.0)
+            }
+        }
+    }
+}
+
+impl CatchObjectives {
+    /// Reward which the next winner of the Objective would get
+    pub fn current_reward(&self, obj_id: ObjectiveId, objective: &Objective) -> Balance {
+        let obj_stat = self
+            .stats
+            .get(obj_id.into())
+            .unwrap_or_else(|| env::panic(b"Invariant : Invalid Objective"));
+
+        objective
+            .reward_curve
+            .reward_at(objective.reward, &obj_stat)
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// MetadataManager Only - Set the Reward Curve of an Objective
+    pub fn set_objective_reward_curve(&mut self, obj_id: ObjectiveId, reward_curve: RewardCurve) {
+        self.assert_role(Role::MetadataManager);
+
+        let mut objective = self.catch_objectives.get_objective(obj_id);
+
+        reward_curve.assert_valid_curve(objective.reward);
+        objective.reward_curve = reward_curve;
+
+        self.catch_objectives
+            .objectives
+            .replace(obj_id.into(), &objective);
+
+        ObjectiveRewardCurveUpdateLog {
+            obj_id,
+            reward_curve: objective.reward_curve,
+        }
+        .emit();
+    }
+
+    /// View Function - returns the reward which the next winner of the Objective would get
+    pub fn get_current_reward(&self, obj_id: ObjectiveId) -> U128 {
+        let objective = self.catch_objectives.get_objective(obj_id);
+
+        U128::from(self.catch_objectives.current_reward(obj_id, &objective))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod reward_curve_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::Balance;
+    use near_sdk::MockedBlockchain;
+
+    fn stats(winner_count: u128, rarity: Rarity) -> ObjectiveStats {
+        ObjectiveStats {
+            winner_count,
+            rarity,
+        }
+    }
+
+    #[test]
+    fn linear_curve() {
+        let curve = RewardCurve::Linear {
+            decrement: U128::from(10),
+            floor: U128::from(500),
+        };
+
+        assert_eq!(curve.reward_at(1000, &stats(0, Rarity::Legendary)), 1000);
+        assert_eq!(curve.reward_at(1000, &stats(20, Rarity::Legendary)), 800);
+        assert_eq!(curve.reward_at(1000, &stats(50_000, Rarity::Common)), 500);
+    }
+
+    #[test]
+    fn step_curve() {
+        let curve = RewardCurve::Step {
+            legendary: U128::from(1000),
+            rare: U128::from(300),
+            common: U128::from(100),
+        };
+
+        assert_eq!(curve.reward_at(0, &stats(0, Rarity::Legendary)), 1000);
+        assert_eq!(curve.reward_at(0, &stats(5000, Rarity::Rare)), 300);
+        assert_eq!(curve.reward_at(0, &stats(50_000, Rarity::Common)), 100);
+    }
+
+    #[test]
+    fn exponential_curve() {
+        let curve = RewardCurve::Exponential {
+            half_life: U128::from(100),
+            floor: U128::from(10),
+        };
+
+        assert_eq!(curve.reward_at(1000, &stats(0, Rarity::Legendary)), 1000);
+        assert_eq!(curve.reward_at(1000, &stats(50, Rarity::Legendary)), 750);
+        assert_eq!(curve.reward_at(1000, &stats(100, Rarity::Legendary)), 500);
+        assert_eq!(curve.reward_at(1000, &stats(200, Rarity::Rare)), 250);
+        assert_eq!(curve.reward_at(1000, &stats(50_000, Rarity::Common)), 10);
+    }
+
+    #[test]
+    fn exponential_curve_large_values() {
+        let reward: Balance = 1_000_000_000_000_000_000_000_000_000_000;
+        let curve = RewardCurve::Exponential {
+            half_life: U128::from(1_000_000_000),
+            floor: U128::from(0),
+        };
+
+        // (reward - reward / 2) * progress is greater than u128::MAX
+        assert_eq!(
+            curve.reward_at(reward, &stats(900_000_000, Rarity::Common)),
+            550_000_000_000_000_000_000_000_000_000
+        );
+        assert_eq!(
+            curve.reward_at(reward, &stats(1_900_000_000, Rarity::Common)),
+            275_000_000_000_000_000_000_000_000_000
+        );
+
+        let curve = RewardCurve::Exponential {
+            half_life: U128::from(u128::MAX),
+            floor: U128::from(0),
+        };
+        assert_eq!(
+            curve.reward_at(u128::MAX, &stats(u128::MAX - 1, Rarity::Common)),
+            u128::MAX / 2 + 1
+        );
+    }
+
+    #[test]
+    fn exponential_curve_last_halvings() {
+        let curve = RewardCurve::Exponential {
+            half_life: U128::from(100),
+            floor: U128::from(0),
+        };
+
+        // 127 halvings leave the highest bit, the next halving reaches 0
+        assert_eq!(
+            curve.reward_at(u128::MAX, &stats(127 * 100, Rarity::Common)),
+            1
+        );
+        assert_eq!(
+            curve.reward_at(u128::MAX, &stats(127 * 100 + 50, Rarity::Common)),
+            1
+        );
+        assert_eq!(
+            curve.reward_at(u128::MAX, &stats(128 * 100, Rarity::Common)),
+            0
+        );
+        assert_eq!(
+            curve.reward_at(u128::MAX, &stats(128 * 100 + 50, Rarity::Common)),
+            0
+        );
+
+        let curve = RewardCurve::Exponential {
+            half_life: U128::from(1),
+            floor: U128::from(7),
+        };
+        assert_eq!(
+            curve.reward_at(u128::MAX, &stats(u128::MAX, Rarity::Common)),
+            7
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Floor can't be greater than the reward")]
+    fn set_reward_curve_invalid() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.set_objective_reward_curve(
+            0,
+            RewardCurve::Linear {
+                decrement: U128::from(1),
+                floor: U128::from(1001),
+            },
+        );
+    }
+
+    #[test]
+    fn rewards_follow_curve() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(alice()));
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[alice(), carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        contract.set_objective_reward_curve(
+            0,
+            RewardCurve::Linear {
+                decrement: U128::from(100),
+                floor: U128::from(0),
+            },
+        );
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(alice().to_string(), vec![0], U64::from(0));
+
+        assert_eq!(contract.get_current_reward(0), U128::from(900));
+        assert_eq!(
+            contract.get_objectives(0, 1, None)[0].current_reward,
+            U128::from(900)
+        );
+
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(0));
+
+        assert_eq!(contract.ft_balance_of(alice()).0, 1000);
+        assert_eq!(contract.ft_balance_of(carol()).0, 900);
+        assert_eq!(contract.get_reward_pool().balance.0, 3100);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::*;
+
+/// Roles which can be granted to accounts for administering the contract
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    /// Can grant and revoke every role
+    Admin,
+
+    /// Can distribute Objective rewards to players, usually the CatchLabs NFT Contract
+    RewardDistributor,
+
+    /// Can create player sub-accounts
+    AccountCreator,
+
+    /// Can pause and unpause the contract
+    Pauser,
+
+    /// Can manage Objectives and their metadata
+    MetadataManager,
+
+    /// Can record the KYC status of players
+    KycVerifier,
+}
+
+impl Role {
+    pub fn all() -> Vec<Role> {
+        vec![
+            Role::Admin,
+            Role::RewardDistributor,
+            Role::AccountCreator,
+            Role::Pauser,
+            Role::MetadataManager,
+            Role::KycVerifier,
+        ]
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Admin Only - Grant the role to the account
+    pub fn grant_role(&mut self, role: Role, account_id: ValidAccountId) {
+        self.assert_role(Role::Admin);
+
+        let account_id: AccountId = account_id.into();
+
+        require!(
+            self.internal_grant_role(role, &account_id),
+            "Account already has the role"
+        );
+
+        RoleGrantLog {
+            role,
+            account_id,
+            sender_id: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    /// Admin Only - Revoke the role from the account
+    pub fn revoke_role(&mut self, role: Role, account_id: ValidAccountId) {
+        self.assert_role(Role::Admin);
+
+        let account_id: AccountId = account_id.into();
+
+        self.internal_revoke_role(role, &account_id);
+
+        RoleRevokeLog {
+            role,
+            account_id,
+            sender_id: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    /// Give up a role held by the caller
+    pub fn renounce_role(&mut self, role: Role) {
+        let account_id = env::predecessor_account_id();
+
+        self.internal_revoke_role(role, &account_id);
+
+        RoleRevokeLog {
+            role,
+            account_id: account_id.clone(),
+            sender_id: account_id,
+        }
+        .emit();
+    }
+
+    /// View Function - returns true if the account has the role
+    pub fn has_role(&self, role: Role, account_id: ValidAccountId) -> bool {
+        self.internal_has_role(role, account_id.as_ref())
+    }
+
+    /// View Function - returns paginated view of accounts having the role
+    pub fn get_role_members(&self, role: Role, from_index: u64, limit: u64) -> Vec<AccountId> {
+        match self.roles.get(&role) {
+            Some(members) => members
+                .iter()
+                .skip(from_index as usize)
+                .take(limit as usize)
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
+/*********************************/
+/*  INTERNAL FUNCTIONS - ROLES  */
+/*********************************/
+
+impl Contract {
+    pub fn assert_role(&self, role: Role) {
+        require!(
+            self.internal_has_role(role, &env::predecessor_account_id()),
+            format!("Only accounts with {:?} role can call this method", role)
+        );
+    }
+
+    pub fn internal_has_role(&self, role: Role, account_id: &AccountId) -> bool {
+        match self.roles.get(&role) {
+            Some(members) => members.contains(account_id),
+            None => false,
+        }
+    }
+
+    /// Returns false if the account already had the role
+    pub fn internal_grant_role(&mut self, role: Role, account_id: &AccountId) -> bool {
+        let mut members = self.roles.get(&role).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::RoleMembers { role }.try_to_vec().unwrap())
+        });
+
+        let granted = members.insert(account_id);
+        self.roles.insert(&role, &members);
+
+        granted
+    }
+
+    pub fn internal_revoke_role(&mut self, role: Role, account_id: &AccountId) {
+        let mut members = self
+            .roles
+            .get(&role)
+            .unwrap_or_else(|| env::panic(b"Account doesn't have the role"));
+
+        require!(members.remove(account_id), "Account doesn't have the role");
+        require!(
+            role != Role::Admin || !members.is_empty(),
+            "Can't remove the last Admin"
+        );
+
+        self.roles.insert(&role, &members);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod roles_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::test_utils;
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    #[test]
+    fn owner_has_roles_after_init() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let contract = create_contract();
+
+        assert!(contract.has_role(Role::Admin, dex()));
+        assert!(contract.has_role(Role::MetadataManager, dex()));
+        assert!(!contract.has_role(Role::RewardDistributor, dex()));
+        assert_eq!(
+            contract.get_role_members(Role::Admin, 0, 10),
+            vec![dex().to_string()]
+        );
+    }
+
+    #[test]
+    fn grant_and_revoke_role() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+
+        testing_env!(get_context(dex().to_string(), 0));
+        contract.grant_role(Role::AccountCreator, carol());
+        assert!(contract.has_role(Role::AccountCreator, carol()));
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"catch","version":"1.0.0","event":"role_grant","data":{"role":"AccountCreator","account_id":"carol.near","sender_id":"dex.near"}}"#
+        );
+
+        contract.revoke_role(Role::AccountCreator, carol());
+        assert!(!contract.has_role(Role::AccountCreator, carol()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only accounts with Admin role can call this method")]
+    fn grant_role_not_admin() {
+        testing_env!(get_context(carol().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.grant_role(Role::Admin, carol());
+    }
+
+    #[test]
+    fn renounce_role() {
+        testing_env!(get_context(nft().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.renounce_role(Role::RewardDistributor);
+
+        assert!(!contract.has_role(Role::RewardDistributor, nft()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't remove the last Admin")]
+    fn renounce_last_admin() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.renounce_role(Role::Admin);
+    }
+}
@@ -0,0 +1,342 @@
+use crate::*;
+
+use crate::catch_game::Objective;
+
+/// Status of an Objective as per its time window, its Season and the current block timestamp
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ObjectiveStatus {
+    /// Window hasn't started yet
+    Upcoming,
+
+    /// Can be rewarded
+    Active,
+
+    /// Window has ended or the Objective is retired
+    Expired,
+}
+
+/// A set of Objectives which are opened and closed together, e.g. a weekend event
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Season {
+    /// Block timestamp in nanoseconds from which Objectives of the Season can be rewarded, None means open right away
+    pub starts_at: Option<U64>,
+
+    /// Block timestamp in nanoseconds from which Objectives of the Season can't be rewarded anymore, None means never closes
+    pub ends_at: Option<U64>,
+}
+
+/// Window is [starts_at, ends_at), missing bounds are open ended
+fn window_status(starts_at: Option<U64>, ends_at: Option<U64>, now: u64) -> ObjectiveStatus {
+    if ends_at.is_some_and(|ends_at| now >= ends_at.0) {
+        ObjectiveStatus::Expired
+    } else if starts_at.is_some_and(|starts_at| now < starts_at.0) {
+        ObjectiveStatus::Upcoming
+    } else {
+        ObjectiveStatus::Active
+    }
+}
+
+fn assert_valid_window(starts_at: Option<U64>, ends_at: Option<U64>) {
+    if let (Some(starts_at), Some(ends_at)) = (starts_at, ends_at) {
+        require!(
+            starts_at.0 < ends_at.0,
+            "starts_at should be less than ends_at"
+        );
+    }
+}
+
+impl CatchObjectives {
+    /// Returns the status of the Objective, it is Active only if both its own window and the window of its Season are
+    pub fn objective_status(&self, objective: &Objective) -> ObjectiveStatus {
+        if objective.retired {
+            return ObjectiveStatus::Expired;
+        }
+
+        let now = env::block_timestamp();
+
+        let obj_status = window_status(objective.starts_at, objective.ends_at, now);
+
+        let season_status = match &objective.season_id {
+            Some(season_id) => {
+                let season = self
+                    .seasons
+                    .get(season_id)
+                    .unwrap_or_else(|| env::panic(b"Invariant : Invalid Season"));
+                window_status(season.starts_at, season.ends_at, now)
+            }
+            None => ObjectiveStatus::Active,
+        };
+
+        match (obj_status, season_status) {
+            (ObjectiveStatus::Expired, _) | (_, ObjectiveStatus::Expired) => {
+                ObjectiveStatus::Expired
+            }
+            (ObjectiveStatus::Upcoming, _) | (_, ObjectiveStatus::Upcoming) => {
+                ObjectiveStatus::Upcoming
+            }
+            _ => ObjectiveStatus::Active,
+        }
+    }
+
+    pub fn assert_valid_schedule(&self, objective: &Objective) {
+        assert_valid_window(objective.starts_at, objective.ends_at);
+
+        if let Some(season_id) = &objective.season_id {
+            require!(
+                self.seasons.get(season_id).is_some(),
+                "Season doesn't exist"
+            );
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// MetadataManager Only - Create a Season or change its window, every Objective of the Season follows the new window
+    pub fn set_season(&mut self, season_id: String, starts_at: Option<U64>, ends_at: Option<U64>) {
+        self.assert_role(Role::MetadataManager);
+
+        require!(!season_id.is_empty(), "Season id can't be empty");
+        assert_valid_window(starts_at, ends_at);
+
+        self.catch_objectives
+            .seasons
+            .insert(&season_id, &Season { starts_at, ends_at });
+
+        SeasonUpdateLog {
+            season_id,
+            starts_at,
+            ends_at,
+        }
+        .emit();
+    }
+
+    /// MetadataManager Only - Close a Season right away, none of its Objectives can be rewarded after this
+    pub fn close_season(&mut self, season_id: String) {
+        self.assert_role(Role::MetadataManager);
+
+        let mut season = self
+            .catch_objectives
+            .seasons
+            .get(&season_id)
+            .unwrap_or_else(|| env::panic(b"Season doesn't exist"));
+
+        let now = env::block_timestamp();
+
+        require!(
+            window_status(season.starts_at, season.ends_at, now) != ObjectiveStatus::Expired,
+            "Season has already ended"
+        );
+
+        // A Season which hasn't started yet is closed without ever being open
+        if season.starts_at.is_some_and(|starts_at| now < starts_at.0) {
+            season.starts_at = Some(U64::from(now));
+        }
+        season.ends_at = Some(U64::from(now));
+
+        self.catch_objectives.seasons.insert(&season_id, &season);
+
+        SeasonUpdateLog {
+            season_id,
+            starts_at: season.starts_at,
+            ends_at: season.ends_at,
+        }
+        .emit();
+    }
+
+    /// MetadataManager Only - Set the time window and Season of an Objective, None fields remove the bound or the Season
+    pub fn set_objective_schedule(
+        &mut self,
+        obj_id: ObjectiveId,
+        starts_at: Option<U64>,
+        ends_at: Option<U64>,
+        season_id: Option<String>,
+    ) {
+        self.assert_role(Role::MetadataManager);
+
+        let mut objective = self.catch_objectives.get_objective(obj_id);
+
+        objective.starts_at = starts_at;
+        objective.ends_at = ends_at;
+        objective.season_id = season_id;
+
+        self.catch_objectives.assert_valid_schedule(&objective);
+        self.catch_objectives
+            .objectives
+            .replace(obj_id.into(), &objective);
+
+        ObjectiveScheduleUpdateLog {
+            obj_id,
+            starts_at: objective.starts_at,
+            ends_at: objective.ends_at,
+            season_id: objective.season_id,
+        }
+        .emit();
+    }
+
+    /// View Function - returns the Season if it exists
+    pub fn get_season(&self, season_id: String) -> Option<Season> {
+        self.catch_objectives.seasons.get(&season_id)
+    }
+
+    /// View Function - returns paginated view of Seasons along with their ids
+    pub fn get_seasons(&self, from_index: u64, limit: u64) -> Vec<(String, Season)> {
+        self.catch_objectives
+            .seasons
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// View Function - returns the current status of the Objective
+    pub fn get_objective_status(&self, obj_id: ObjectiveId) -> ObjectiveStatus {
+        let objective = self.catch_objectives.get_objective(obj_id);
+        self.catch_objectives.objective_status(&objective)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod seasons_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    #[test]
+    fn window_status() {
+        let (starts_at, ends_at) = (Some(U64::from(10)), Some(U64::from(20)));
+
+        assert_eq!(
+            super::window_status(starts_at, ends_at, 9),
+            ObjectiveStatus::Upcoming
+        );
+        assert_eq!(
+            super::window_status(starts_at, ends_at, 10),
+            ObjectiveStatus::Active
+        );
+        assert_eq!(
+            super::window_status(starts_at, ends_at, 20),
+            ObjectiveStatus::Expired
+        );
+        assert_eq!(
+            super::window_status(None, None, 20),
+            ObjectiveStatus::Active
+        );
+    }
+
+    #[test]
+    fn objective_schedule() {
+        at(dex(), 0, 0);
+
+        let mut contract = create_contract();
+        contract.set_objective_schedule(1, Some(U64::from(10)), Some(U64::from(20)), None);
+
+        assert_eq!(contract.get_objective_status(0), ObjectiveStatus::Active);
+        assert_eq!(contract.get_objective_status(1), ObjectiveStatus::Upcoming);
+
+        at(dex(), 0, 15);
+        assert_eq!(contract.get_objective_status(1), ObjectiveStatus::Active);
+
+        at(dex(), 0, 20);
+        assert_eq!(contract.get_objective_status(1), ObjectiveStatus::Expired);
+
+        let expired = contract.get_objectives(0, 10, Some(ObjectiveStatus::Expired));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id, 1);
+        assert_eq!(expired[0].status, ObjectiveStatus::Expired);
+    }
+
+    #[test]
+    #[should_panic(expected = "starts_at should be less than ends_at")]
+    fn objective_schedule_invalid() {
+        at(dex(), 0, 0);
+
+        let mut contract = create_contract();
+        contract.set_objective_schedule(1, Some(U64::from(20)), Some(U64::from(20)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Season doesn't exist")]
+    fn objective_schedule_invalid_season() {
+        at(dex(), 0, 0);
+
+        let mut contract = create_contract();
+        contract.set_objective_schedule(1, None, None, Some("weekend".to_string()));
+    }
+
+    #[test]
+    fn season_opens_and_closes_objectives() {
+        at(dex(), 0, 0);
+
+        let mut contract = create_contract();
+        contract.set_season("weekend".to_string(), Some(U64::from(10)), None);
+        contract.set_objective_schedule(0, None, None, Some("weekend".to_string()));
+        contract.set_objective_schedule(1, None, None, Some("weekend".to_string()));
+
+        assert_eq!(
+            contract
+                .get_objectives(0, 10, Some(ObjectiveStatus::Upcoming))
+                .len(),
+            2
+        );
+
+        at(dex(), 0, 10);
+        assert_eq!(
+            contract
+                .get_objectives(0, 10, Some(ObjectiveStatus::Active))
+                .len(),
+            2
+        );
+
+        at(dex(), 0, 30);
+        contract.close_season("weekend".to_string());
+
+        assert_eq!(
+            contract.get_season("weekend".to_string()),
+            Some(Season {
+                starts_at: Some(U64::from(10)),
+                ends_at: Some(U64::from(30)),
+            })
+        );
+        assert!(contract
+            .get_objectives(0, 10, Some(ObjectiveStatus::Active))
+            .is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Objective hasn't started yet")]
+    fn transfer_reward_upcoming_objective() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+        contract.set_objective_schedule(0, Some(U64::from(10)), None, None);
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Objective has ended")]
+    fn transfer_reward_closed_season() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+        contract.set_season("weekend".to_string(), None, None);
+        contract.set_objective_schedule(0, None, None, Some("weekend".to_string()));
+        contract.close_season("weekend".to_string());
+
+        testing_env!(get_context(nft().to_string(), 0));
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(0));
+    }
+}
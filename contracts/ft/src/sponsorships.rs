@@ -0,0 +1,744 @@
+use crate::*;
+
+use crate::catch_game::{Objective, ObjectiveMetadata, ObjectiveStats};
+
+/// Max length in bytes of the title of a sponsored Objective
+const MAX_TITLE_LENGTH: usize = 100;
+
+/// Max proposals a sponsor can have waiting for approval at once
+const MAX_OPEN_PROPOSALS: u32 = 4;
+
+/// Identifier of a sponsorship waiting for approval
+pub type ProposalId = u64;
+
+/// msg of the ft_transfer_call() made to this contract by a sponsor, it describes the sponsored Objective
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorshipMsg {
+    pub title: String,
+
+    /// Tokens paid from the escrow to every player rewarded for the Objective
+    pub reward: U128,
+
+    pub obj_metadata: ObjectiveMetadata,
+    pub starts_at: Option<U64>,
+
+    /// Sponsored Objectives always expire, the sponsor can withdraw the unspent tokens after that
+    pub ends_at: U64,
+
+    /// Location of the business on the Catch Map
+    #[serde(default)]
+    pub location: Option<GeoLocation>,
+}
+
+/// Tokens escrowed by a sponsor for the rewards of an Objective
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Sponsorship {
+    pub sponsor_id: AccountId,
+
+    /// Tokens which are not spent on rewards yet
+    pub balance: Balance,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorshipView {
+    pub sponsor_id: AccountId,
+    pub balance: U128,
+}
+
+/// Sponsored Objective which is added only once a MetadataManager approves it
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SponsorshipProposal {
+    pub sponsor_id: AccountId,
+
+    /// Tokens escrowed for the Objective, refunded if the proposal is rejected
+    pub amount: Balance,
+
+    pub objective: Objective,
+    pub obj_metadata: ObjectiveMetadata,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorshipProposalView {
+    pub id: ProposalId,
+    pub sponsor_id: AccountId,
+    pub amount: U128,
+    pub objective: Objective,
+    pub obj_metadata: ObjectiveMetadata,
+}
+
+/// Escrows of the sponsored Objectives, the tokens are held by the contract account and are not part of the reward pool
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Sponsorships {
+    pub escrows: LookupMap<ObjectiveId, Sponsorship>,
+
+    /// Sponsorships waiting for approval, their tokens are held by the contract account too
+    pub proposals: UnorderedMap<ProposalId, SponsorshipProposal>,
+
+    pub next_proposal_id: ProposalId,
+
+    /// Sponsor -> number of their proposals waiting for approval
+    pub open_proposals: LookupMap<AccountId, u32>,
+}
+
+impl Default for Sponsorships {
+    fn default() -> Self {
+        Self {
+            escrows: LookupMap::new(StorageKey::Sponsorships.try_to_vec().unwrap()),
+            proposals: UnorderedMap::new(StorageKey::SponsorshipProposals.try_to_vec().unwrap()),
+            next_proposal_id: 0,
+            open_proposals: LookupMap::new(StorageKey::SponsorOpenProposals.try_to_vec().unwrap()),
+        }
+    }
+}
+
+impl Sponsorships {
+    pub fn is_sponsored(&self, obj_id: ObjectiveId) -> bool {
+        self.escrows.contains_key(&obj_id)
+    }
+
+    /// Returns the exhausted budget if the escrow of the sponsored Objective can't pay the reward
+    pub fn check_spend(
+        &self,
+        obj_id: ObjectiveId,
+        amount: Balance,
+    ) -> Result<(), RewardBudgetExhaustedLog> {
+        match self.escrows.get(&obj_id) {
+            Some(sponsorship) if sponsorship.balance < amount => Err(RewardBudgetExhaustedLog {
+                budget: "sponsorship".to_string(),
+                obj_id: Some(obj_id),
+                requested: U128::from(amount),
+                remaining: U128::from(sponsorship.balance),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds the proposal and counts it for the sponsor, returns its id
+    pub fn insert_proposal(&mut self, proposal: &SponsorshipProposal) -> ProposalId {
+        let open_proposals = self.open_proposals.get(&proposal.sponsor_id).unwrap_or(0);
+        require!(
+            open_proposals < MAX_OPEN_PROPOSALS,
+            format!(
+                "A sponsor can have at most {} proposals waiting for approval",
+                MAX_OPEN_PROPOSALS
+            )
+        );
+        self.open_proposals
+            .insert(&proposal.sponsor_id, &(open_proposals + 1));
+
+        let proposal_id = self.next_proposal_id;
+        self.next_proposal_id += 1;
+        self.proposals.insert(&proposal_id, proposal);
+
+        proposal_id
+    }
+
+    /// Removes the proposal once it is approved or rejected
+    pub fn remove_proposal(&mut self, proposal_id: ProposalId) -> Option<SponsorshipProposal> {
+        let proposal = self.proposals.remove(&proposal_id)?;
+
+        match self.open_proposals.get(&proposal.sponsor_id).unwrap_or(0) {
+            0 | 1 => self.open_proposals.remove(&proposal.sponsor_id),
+            open_proposals => self
+                .open_proposals
+                .insert(&proposal.sponsor_id, &(open_proposals - 1)),
+        };
+
+        Some(proposal)
+    }
+
+    /// Takes the reward out of the escrow of the Objective
+    pub fn spend(&mut self, obj_id: ObjectiveId, amount: Balance) {
+        if let Err(exhausted) = self.check_spend(obj_id, amount) {
+            env::panic(exhausted.reason().as_bytes());
+        }
+
+        let mut sponsorship = self
+            .escrows
+            .get(&obj_id)
+            .unwrap_or_else(|| env::panic(b"Objective is not sponsored"));
+
+        sponsorship.balance -= amount;
+        self.escrows.insert(&obj_id, &sponsorship);
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Called by ft_transfer_call() when CATCH is sent to this contract, escrows the tokens for a new sponsored Objective
+    ///
+    /// The Objective is proposed and added only once a MetadataManager approves it with approve_sponsorship()
+    ///
+    /// An invalid msg panics, so the tokens are refunded by ft_resolve_transfer()
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: ValidAccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        require!(
+            env::predecessor_account_id() == env::current_account_id(),
+            "Only CATCH can be used to sponsor Objectives"
+        );
+
+        let sponsor_id: AccountId = sender_id.into();
+        let amount: Balance = amount.into();
+
+        let msg: SponsorshipMsg = near_sdk::serde_json::from_str(&msg)
+            .unwrap_or_else(|_| env::panic(b"Invalid sponsorship msg"));
+        let reward: Balance = msg.reward.into();
+
+        require!(
+            !msg.title.is_empty() && msg.title.len() <= MAX_TITLE_LENGTH,
+            format!("Title should be 1 to {} bytes long", MAX_TITLE_LENGTH)
+        );
+        require!(reward > 0, "The reward should be a positive number");
+        require!(
+            amount >= reward,
+            "Sponsored amount should cover at least one reward"
+        );
+        require!(
+            msg.ends_at.0 > env::block_timestamp(),
+            "Sponsored Objective should end in the future"
+        );
+        msg.obj_metadata.assert_valid_metadata();
+        require!(
+            msg.obj_metadata.has_valid_reference(),
+            "Reference should be an ipfs:// link"
+        );
+
+        let objective = Objective {
+            title: msg.title,
+            reward,
+            retired: false,
+            starts_at: msg.starts_at,
+            ends_at: Some(msg.ends_at),
+            season_id: None,
+            prerequisites: vec![],
+            quest_line: None,
+            reward_curve: RewardCurve::Flat,
+            vesting: None,
+            category: None,
+            tags: vec![],
+            location: msg.location,
+        };
+        self.catch_objectives.assert_valid_schedule(&objective);
+        objective.assert_valid_location();
+
+        // Tokens received by ft_transfer_call() stay with the contract account, the proposal only tracks them
+        let proposal_id = self.sponsorships.insert_proposal(&SponsorshipProposal {
+            sponsor_id: sponsor_id.clone(),
+            amount,
+            objective,
+            obj_metadata: msg.obj_metadata,
+        });
+
+        SponsorshipProposeLog {
+            proposal_id,
+            sponsor_id,
+            amount: U128::from(amount),
+        }
+        .emit();
+
+        PromiseOrValue::Value(U128::from(0))
+    }
+
+    /// MetadataManager Only - Add the proposed Objective, its tokens become the escrow of the Objective
+    ///
+    /// returns index of the newly added Objective
+    pub fn approve_sponsorship(&mut self, proposal_id: ProposalId) -> ObjectiveId {
+        self.assert_role(Role::MetadataManager);
+
+        let proposal = self
+            .sponsorships
+            .remove_proposal(proposal_id)
+            .unwrap_or_else(|| env::panic(b"Sponsorship proposal doesn't exist"));
+        let objective = proposal.objective;
+
+        require!(
+            self.catch_objectives.objective_status(&objective) != ObjectiveStatus::Expired,
+            "Sponsored Objective has already ended"
+        );
+
+        let stats = ObjectiveStats {
+            winner_count: 0,
+            rarity: Rarity::Legendary,
+        };
+
+        let obj_id =
+            self.catch_objectives
+                .add_objective(&objective, &proposal.obj_metadata, &stats);
+        self.catch_objectives
+            .refresh_rarity(obj_id, self.token.registered_accounts);
+
+        self.sponsorships.escrows.insert(
+            &obj_id,
+            &Sponsorship {
+                sponsor_id: proposal.sponsor_id.clone(),
+                balance: proposal.amount,
+            },
+        );
+
+        ObjectiveAddLog {
+            obj_id,
+            title: objective.title,
+            reward: U128::from(objective.reward),
+        }
+        .emit();
+
+        SponsorshipCreateLog {
+            obj_id,
+            sponsor_id: proposal.sponsor_id,
+            amount: U128::from(proposal.amount),
+        }
+        .emit();
+
+        obj_id
+    }
+
+    /// MetadataManager or Sponsor Only - Drop the proposal and refund its tokens to the sponsor
+    pub fn reject_sponsorship(&mut self, proposal_id: ProposalId) -> U128 {
+        self.assert_not_paused(PauseFlag::Transfers);
+
+        let proposal = self
+            .sponsorships
+            .proposals
+            .get(&proposal_id)
+            .unwrap_or_else(|| env::panic(b"Sponsorship proposal doesn't exist"));
+
+        let sender_id = env::predecessor_account_id();
+        if sender_id != proposal.sponsor_id {
+            self.assert_role(Role::MetadataManager);
+        }
+        self.token.assert_can_receive(&proposal.sponsor_id);
+
+        self.sponsorships.remove_proposal(proposal_id);
+        self.token.internal_transfer(
+            &env::current_account_id(),
+            &proposal.sponsor_id,
+            proposal.amount,
+            Some("sponsorship".to_string()),
+        );
+
+        SponsorshipRejectLog {
+            proposal_id,
+            sponsor_id: proposal.sponsor_id,
+            amount: U128::from(proposal.amount),
+            sender_id,
+        }
+        .emit();
+
+        U128::from(proposal.amount)
+    }
+
+    /// Sponsor Only - Move the unspent tokens of a sponsored Objective to the sponsor's balance once the Objective has expired
+    pub fn withdraw_sponsorship(&mut self, obj_id: ObjectiveId) -> U128 {
+        self.assert_not_paused(PauseFlag::Transfers);
+
+        let sponsorship = self
+            .sponsorships
+            .escrows
+            .get(&obj_id)
+            .unwrap_or_else(|| env::panic(b"Objective is not sponsored"));
+
+        require!(
+            env::predecessor_account_id() == sponsorship.sponsor_id,
+            "Only the sponsor can withdraw the sponsorship"
+        );
+
+        let objective = self.catch_objectives.get_objective(obj_id);
+        require!(
+            self.catch_objectives.objective_status(&objective) == ObjectiveStatus::Expired,
+            "Sponsored Objective hasn't expired yet"
+        );
+        require!(sponsorship.balance > 0, "Nothing to withdraw");
+        self.token.assert_can_receive(&sponsorship.sponsor_id);
+
+        // Escrow is kept with a zero balance, so that the Objective is still paid only from it
+        self.sponsorships.escrows.insert(
+            &obj_id,
+            &Sponsorship {
+                sponsor_id: sponsorship.sponsor_id.clone(),
+                balance: 0,
+            },
+        );
+        self.token.internal_transfer(
+            &env::current_account_id(),
+            &sponsorship.sponsor_id,
+            sponsorship.balance,
+            Some("sponsorship".to_string()),
+        );
+
+        SponsorshipWithdrawLog {
+            obj_id,
+            sponsor_id: sponsorship.sponsor_id,
+            amount: U128::from(sponsorship.balance),
+        }
+        .emit();
+
+        U128::from(sponsorship.balance)
+    }
+
+    /// View Function - returns paginated view of the sponsorships waiting for approval
+    pub fn get_sponsorship_proposals(
+        &self,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<SponsorshipProposalView> {
+        self.sponsorships
+            .proposals
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .map(|(id, proposal)| SponsorshipProposalView {
+                id,
+                sponsor_id: proposal.sponsor_id,
+                amount: U128::from(proposal.amount),
+                objective: proposal.objective,
+                obj_metadata: proposal.obj_metadata,
+            })
+            .collect()
+    }
+
+    /// View Function - returns the sponsor and the unspent tokens of a sponsored Objective
+    pub fn get_sponsorship(&self, obj_id: ObjectiveId) -> Option<SponsorshipView> {
+        self.sponsorships
+            .escrows
+            .get(&obj_id)
+            .map(|sponsorship| SponsorshipView {
+                sponsor_id: sponsorship.sponsor_id,
+                balance: U128::from(sponsorship.balance),
+            })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod sponsorships_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::Balance;
+    use near_sdk::MockedBlockchain;
+
+    fn sponsorship_msg(reward: Balance, ends_at: u64) -> String {
+        near_sdk::serde_json::to_string(&SponsorshipMsg {
+            title: String::from("Visit the Cafe"),
+            reward: U128::from(reward),
+            obj_metadata: ObjectiveMetadata {
+                reference: "ipfs://cafe".to_string(),
+                reference_hash: Base64VecU8::from([3_u8; 32].to_vec()),
+            },
+            starts_at: None,
+            ends_at: U64::from(ends_at),
+            location: None,
+        })
+        .unwrap()
+    }
+
+    /// alice proposes a sponsorship with 1000 tokens, 400 per reward, ending at 100
+    fn create_proposed_contract() -> Contract {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(alice()));
+        contract.storage_deposit(Some(carol()));
+        contract.storage_deposit(Some(bob()));
+        contract.storage_deposit(Some(dex()));
+        verify_players(&mut contract, &[alice(), carol(), bob(), dex()]);
+        contract
+            .token
+            .internal_transfer(&dex().into(), &alice().into(), 1000, None);
+
+        // Tokens of ft_transfer_call() are already with the contract account when ft_on_transfer() is called
+        contract
+            .token
+            .internal_transfer(&alice().into(), &env::current_account_id(), 1000, None);
+
+        at(mike(), 0, 0);
+        contract.ft_on_transfer(alice(), U128::from(1000), sponsorship_msg(400, 100));
+
+        contract
+    }
+
+    /// alice sponsors Objective2 with 1000 tokens, 400 per reward, ending at 100
+    fn create_sponsored_contract() -> Contract {
+        let mut contract = create_proposed_contract();
+
+        at(dex(), 0, 0);
+        assert_eq!(contract.approve_sponsorship(0), 2);
+
+        contract
+    }
+
+    fn reward(contract: &mut Contract, player_id: ValidAccountId) {
+        at(nft(), 0, 10);
+
+        let player_id = player_id.to_string();
+        let nonce = contract.get_reward_nonce(player_id.clone());
+        contract.transfer_player_reward(player_id, vec![2], nonce);
+    }
+
+    #[test]
+    fn sponsored_objective_pays_from_escrow() {
+        let mut contract = create_sponsored_contract();
+
+        assert_eq!(contract.get_objective_count(), 3);
+        assert_eq!(contract.ft_balance_of(alice()).0, 0);
+        assert_eq!(contract.get_reward_pool().balance, U128::from(0));
+
+        reward(&mut contract, carol());
+        reward(&mut contract, bob());
+
+        assert_eq!(contract.ft_balance_of(carol()).0, 400);
+        assert_eq!(contract.ft_balance_of(bob()).0, 400);
+        assert_eq!(contract.ft_balance_of(mike()).0, 200);
+        assert_eq!(
+            contract.get_sponsorship(2),
+            Some(SponsorshipView {
+                sponsor_id: alice().to_string(),
+                balance: U128::from(200),
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Sponsorship doesn't have enough balance")]
+    fn sponsored_objective_escrow_exhausted() {
+        let mut contract = create_sponsored_contract();
+
+        // Reward pool has tokens, but they are never used for a sponsored Objective
+        testing_env!(get_context(dex().to_string(), 0));
+        seed_reward_pool(&mut contract, 5000);
+
+        reward(&mut contract, carol());
+        reward(&mut contract, bob());
+        reward(&mut contract, dex());
+    }
+
+    #[test]
+    fn withdraw_sponsorship() {
+        let mut contract = create_sponsored_contract();
+
+        reward(&mut contract, carol());
+
+        at(alice(), 0, 100);
+        assert_eq!(contract.withdraw_sponsorship(2), U128::from(600));
+        assert_eq!(contract.ft_balance_of(alice()).0, 600);
+        assert_eq!(contract.get_sponsorship(2).unwrap().balance, U128::from(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Account alice.near is frozen for incoming transfers")]
+    fn withdraw_sponsorship_frozen() {
+        let mut contract = create_sponsored_contract();
+        contract.freeze_account(alice(), FreezeDirection::Incoming, "LR-017".to_string());
+
+        at(alice(), 0, 100);
+        contract.withdraw_sponsorship(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account alice.near is frozen for incoming transfers")]
+    fn reject_sponsorship_frozen() {
+        let mut contract = create_proposed_contract();
+
+        at(dex(), 0, 0);
+        contract.freeze_account(alice(), FreezeDirection::Both, "LR-017".to_string());
+        contract.reject_sponsorship(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Transfers are paused")]
+    fn withdraw_sponsorship_paused() {
+        let mut contract = create_sponsored_contract();
+        contract.pause(vec![PauseFlag::Transfers], "Incident".to_string());
+
+        at(alice(), 0, 100);
+        contract.withdraw_sponsorship(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sponsored Objective hasn't expired yet")]
+    fn withdraw_sponsorship_before_expiry() {
+        let mut contract = create_sponsored_contract();
+
+        at(alice(), 0, 99);
+        contract.withdraw_sponsorship(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only CATCH can be used to sponsor Objectives")]
+    fn ft_on_transfer_from_another_token() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        contract.ft_on_transfer(alice(), U128::from(1000), sponsorship_msg(400, 100));
+    }
+
+    #[test]
+    fn proposal_is_added_once_approved() {
+        let mut contract = create_proposed_contract();
+
+        assert_eq!(contract.get_objective_count(), 2);
+        assert!(contract.get_sponsorship(2).is_none());
+        assert_eq!(contract.ft_balance_of(mike()).0, 1000);
+
+        let proposals = contract.get_sponsorship_proposals(0, 10);
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].sponsor_id, alice().to_string());
+        assert_eq!(proposals[0].objective.title, "Visit the Cafe");
+
+        at(dex(), 0, 0);
+        contract.approve_sponsorship(0);
+
+        assert_eq!(contract.get_objective_count(), 3);
+        assert!(contract.get_sponsorship_proposals(0, 10).is_empty());
+        assert_eq!(
+            contract.get_sponsorship(2),
+            Some(SponsorshipView {
+                sponsor_id: alice().to_string(),
+                balance: U128::from(1000),
+            })
+        );
+    }
+
+    #[test]
+    fn reject_sponsorship_refunds() {
+        let mut contract = create_proposed_contract();
+
+        at(dex(), 0, 0);
+        assert_eq!(contract.reject_sponsorship(0), U128::from(1000));
+
+        assert_eq!(contract.ft_balance_of(alice()).0, 1000);
+        assert_eq!(contract.ft_balance_of(mike()).0, 0);
+        assert_eq!(contract.get_objective_count(), 2);
+        assert!(contract.get_sponsorship_proposals(0, 10).is_empty());
+    }
+
+    #[test]
+    fn sponsor_withdraws_proposal() {
+        let mut contract = create_proposed_contract();
+
+        at(alice(), 0, 0);
+        contract.reject_sponsorship(0);
+        assert_eq!(contract.ft_balance_of(alice()).0, 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only accounts with MetadataManager role can call this method")]
+    fn reject_sponsorship_not_sponsor() {
+        let mut contract = create_proposed_contract();
+
+        at(carol(), 0, 0);
+        contract.reject_sponsorship(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only accounts with MetadataManager role can call this method")]
+    fn approve_sponsorship_by_sponsor() {
+        let mut contract = create_proposed_contract();
+
+        at(alice(), 0, 0);
+        contract.approve_sponsorship(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sponsored Objective has already ended")]
+    fn approve_sponsorship_after_end() {
+        let mut contract = create_proposed_contract();
+
+        at(dex(), 0, 100);
+        contract.approve_sponsorship(0);
+    }
+
+    /// ft_on_transfer() of alice with the msg changed by edit
+    fn propose_with(edit: impl FnOnce(&mut SponsorshipMsg)) {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        let mut msg: SponsorshipMsg =
+            near_sdk::serde_json::from_str(&sponsorship_msg(400, 100)).unwrap();
+        edit(&mut msg);
+
+        testing_env!(get_context(env::current_account_id(), 0));
+        contract.ft_on_transfer(
+            alice(),
+            U128::from(1000),
+            near_sdk::serde_json::to_string(&msg).unwrap(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Hash has to be 32 bytes")]
+    fn ft_on_transfer_invalid_hash() {
+        propose_with(|msg| msg.obj_metadata.reference_hash = Base64VecU8::from(vec![3_u8; 16]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Reference should be an ipfs:// link")]
+    fn ft_on_transfer_invalid_reference() {
+        propose_with(|msg| msg.obj_metadata.reference = "https://cafe".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Latitude should be between -90 and 90 degrees")]
+    fn ft_on_transfer_invalid_location() {
+        propose_with(|msg| {
+            msg.location = Some(GeoLocation {
+                lat: 91_000_000,
+                lng: 0,
+                radius: 50,
+                region: "IN-MH".to_string(),
+            })
+        });
+    }
+
+    #[test]
+    fn open_proposals_are_capped() {
+        let mut contract = create_proposed_contract();
+
+        let propose = |contract: &mut Contract| {
+            at(mike(), 0, 0);
+            contract.ft_on_transfer(alice(), U128::from(400), sponsorship_msg(400, 100))
+        };
+        for _ in 1..MAX_OPEN_PROPOSALS {
+            propose(&mut contract);
+        }
+        assert_eq!(
+            contract.sponsorships.open_proposals.get(&alice().into()),
+            Some(4)
+        );
+
+        // Approved and rejected proposals free up room for new ones
+        at(dex(), 0, 0);
+        contract.approve_sponsorship(0);
+        contract.reject_sponsorship(1);
+        propose(&mut contract);
+        assert_eq!(
+            contract.sponsorships.open_proposals.get(&alice().into()),
+            Some(3)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "A sponsor can have at most 4 proposals waiting for approval")]
+    fn open_proposals_cap_reached() {
+        let mut contract = create_proposed_contract();
+
+        for _ in 0..MAX_OPEN_PROPOSALS {
+            at(mike(), 0, 0);
+            contract.ft_on_transfer(alice(), U128::from(400), sponsorship_msg(400, 100));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Title should be 1 to 100 bytes long")]
+    fn ft_on_transfer_long_title() {
+        propose_with(|msg| msg.title = "a".repeat(MAX_TITLE_LENGTH + 1));
+    }
+}
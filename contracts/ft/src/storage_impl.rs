@@ -3,8 +3,8 @@ use crate::*;
 #[derive(Serialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct StorageBalance {
-    total: U128,
-    available: U128,
+    pub total: U128,
+    pub available: U128,
 }
 
 #[derive(Serialize)]
@@ -23,6 +23,11 @@ pub trait StorageManager {
     /// Removes the A/c if no tokens present, burns token only if force = true
     fn storage_unregister(&mut self, force: Option<bool>) -> bool;
 
+    /// Wallet UX Security -> Attach 1 Yocto,
+    ///
+    /// Withdraws the part of the storage deposit not used by allowances, all of it if amount is None
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance;
+
     /// Returns min and max NEAR that can be deposited for storage,
     ///
     /// Here min = max
@@ -46,27 +51,44 @@ impl StorageManager for Contract {
 
         let account_id: AccountId = match account_id {
             Some(acc_id) => acc_id.into(),
-            None => env::predecessor_account_id().into(),
+            None => env::predecessor_account_id(),
         };
 
         if self.token.accounts.contains_key(&account_id) {
-            log!("The account is already registered, refunding the deposit");
+            // Deposits of registered accounts pay for the storage of their reward records and allowances
             if amount > 0 {
-                Promise::new(env::predecessor_account_id()).transfer(amount);
+                let mut storage_account = self
+                    .token
+                    .storage_accounts
+                    .get(&account_id)
+                    .unwrap_or_default();
+                storage_account.deposit += amount;
+                self.token
+                    .storage_accounts
+                    .insert(&account_id, &storage_account);
             }
         } else {
+            self.assert_not_paused(PauseFlag::Registrations);
+
             let min_balance = self.storage_balance_bounds().min.0;
             require!(
                 amount >= min_balance,
                 format!("Please Attach a deposit of {} Yocto Near", min_balance)
             );
 
-            self.token.accounts.insert(&account_id, &0);
+            self.token.internal_register_account(&account_id);
 
-            let refund = amount - min_balance;
+            // Rest of the deposit pays for the Objectives rewarded to the account and its allowances
+            let extra = amount - min_balance;
 
-            if refund > 0 {
-                Promise::new(env::predecessor_account_id()).transfer(refund);
+            if extra > 0 {
+                self.token.storage_accounts.insert(
+                    &account_id,
+                    &StorageAccount {
+                        deposit: extra,
+                        ..Default::default()
+                    },
+                );
             }
         }
         self.internal_storage_balance_of(&account_id).unwrap()
@@ -77,12 +99,47 @@ impl StorageManager for Contract {
         self.internal_storage_unregister(force).is_some()
     }
 
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        require!(
+            self.token.accounts.contains_key(&account_id),
+            "The account is not registered"
+        );
+
+        let mut storage_account = self
+            .token
+            .storage_accounts
+            .get(&account_id)
+            .unwrap_or_default();
+        let available = storage_account.available();
+        let amount = amount.map_or(available, |amount| amount.into());
+
+        require!(
+            amount <= available,
+            "The amount is greater than the available storage balance"
+        );
+
+        if amount > 0 {
+            storage_account.deposit -= amount;
+            self.token
+                .storage_accounts
+                .insert(&account_id, &storage_account);
+            Promise::new(account_id.clone()).transfer(amount);
+        }
+
+        self.internal_storage_balance_of(&account_id).unwrap()
+    }
+
+    /// Max is None as registered accounts can deposit more for the storage of their allowances
     fn storage_balance_bounds(&self) -> StorageBalanceBounds {
         let required_storage_balance =
             Balance::from(self.account_storage_usage) * env::storage_byte_cost();
         StorageBalanceBounds {
             min: required_storage_balance.into(),
-            max: Some(required_storage_balance.into()),
+            max: None,
         }
     }
 
@@ -101,14 +158,50 @@ impl Contract {
         force: Option<bool>,
     ) -> Option<(AccountId, Balance)> {
         assert_one_yocto();
+        self.assert_not_paused(PauseFlag::Registrations);
         let account_id = env::predecessor_account_id();
         let force = force.unwrap_or(false);
+
+        // Frozen balance can't be burnt away by unregistering
+        self.token.assert_can_send(&account_id);
+
+        // Claimed vouchers are forgotten along with the account, so they shouldn't be claimable again
+        require!(
+            env::block_timestamp() >= self.vouchers.claims_expire_at(&account_id),
+            "Can't unregister the account before its claimed vouchers expire"
+        );
+
         if let Some(balance) = self.token.accounts.get(&account_id) {
-            if balance == 0 || force {
-                self.token.accounts.remove(&account_id);
+            let (claimable, locked) = self.token.vesting_balance_of(&account_id);
+
+            if (balance == 0 && claimable + locked == 0) || force {
+                self.token.internal_unregister_account(&account_id);
+
+                // Locked tokens are held by the contract account, they are burnt along with the balance
+                let locked = self.token.internal_remove_vesting(&account_id);
+                if locked > 0 {
+                    self.token
+                        .internal_withdraw(&env::current_account_id(), locked);
+
+                    FtBurnLog {
+                        owner_id: env::current_account_id(),
+                        amount: U128::from(locked),
+                        memo: Some(format!("Locked rewards of {} burnt", account_id)),
+                    }
+                    .emit();
+                }
+
+                // Reward records are removed, so the account starts afresh if it registers again
+                self.catch_objectives.remove_player_records(&account_id);
+                let season_ids = self.catch_objectives.seasons.keys().collect();
+                self.leaderboard.remove_player(&account_id, season_ids);
+                self.vouchers.remove_player(&account_id);
+
+                // Allowances given by the account are removed and its storage deposit is refunded
+                let storage_deposit = self.token.internal_remove_allowances(&account_id);
 
                 // no need to check as balance subtracted will always be valid
-                self.token.total_supply -= balance;
+                self.token.total_supply -= balance + locked;
 
                 FtBurnLog {
                     owner_id: account_id.to_string(),
@@ -117,8 +210,9 @@ impl Contract {
                 }
                 .emit();
 
-                Promise::new(account_id.clone()).transfer(self.storage_balance_bounds().min.0 + 1);
-                Some((account_id, balance))
+                Promise::new(account_id.clone())
+                    .transfer(self.storage_balance_bounds().min.0 + 1 + storage_deposit);
+                Some((account_id, balance + locked))
             } else {
                 env::panic(b"Can't unregister the account with the positive balance without force")
             }
@@ -130,9 +224,15 @@ impl Contract {
 
     pub fn internal_storage_balance_of(&self, account_id: &AccountId) -> Option<StorageBalance> {
         if self.token.accounts.contains_key(account_id) {
+            let storage_account = self
+                .token
+                .storage_accounts
+                .get(account_id)
+                .unwrap_or_default();
+
             Some(StorageBalance {
-                total: self.storage_balance_bounds().min,
-                available: 0.into(),
+                total: (self.storage_balance_bounds().min.0 + storage_account.deposit).into(),
+                available: storage_account.available().into(),
             })
         } else {
             None
@@ -150,11 +250,8 @@ mod storage_tests {
     use near_sdk::Balance;
     use near_sdk::MockedBlockchain;
 
-    const ONE_YOCTO: Balance = 1;
-    const STORAGE_COST: Balance = 1_250_000_000_000_000_000_000; // 1 Near = 10^24 Yocto Near
-
     #[test]
-    #[should_panic(expected = "Please Attach a deposit of 1250000000000000000000 Yocto Near")]
+    #[should_panic(expected = "Please Attach a deposit of 10330000000000000000000 Yocto Near")]
     fn storage_deposit_fails() {
         testing_env!(get_context(carol().to_string(), 500));
         let mut contract = create_contract();
@@ -197,6 +294,89 @@ mod storage_tests {
         assert!(contract.storage_unregister(Some(true)));
         assert_eq!(contract.ft_balance_of(dex()).0, remaining_balance);
         assert_eq!(contract.ft_balance_of(carol()).0, 0);
-        assert!(!contract.storage_balance_of(carol()).is_some());
+        assert!(contract.storage_balance_of(carol()).is_none());
+    }
+
+    #[test]
+    fn storage_deposit_pays_for_reward_records() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+        let mut contract = create_contract();
+        seed_reward_pool(&mut contract, 5000);
+        contract.internal_grant_role(Role::RewardDistributor, &dex().into());
+        verify_players(&mut contract, &[carol()]);
+        contract.set_season("S1".to_string(), None, None);
+        contract.set_objective_schedule(1, None, None, Some("S1".to_string()));
+
+        let initial_storage_usage = env::storage_usage();
+        contract.storage_deposit(Some(carol()));
+        contract.transfer_player_reward(carol().to_string(), vec![0, 1], U64::from(0));
+
+        // Registration deposit pays for the account, the rest of the deposit for the rewarded Objectives
+        let reward_storage_usage = 2 * contract.reward_storage_usage;
+        assert!(
+            env::storage_usage() - initial_storage_usage
+                <= contract.account_storage_usage + reward_storage_usage
+        );
+        assert_eq!(
+            contract.storage_balance_of(carol()).unwrap().available.0,
+            STORAGE_COST
+                - contract.storage_balance_bounds().min.0
+                - Balance::from(reward_storage_usage) * env::storage_byte_cost()
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Not enough storage deposit for the reward records, top it up with storage_deposit"
+    )]
+    fn transfer_reward_without_storage_deposit() {
+        testing_env!(get_context(dex().to_string(), 0));
+        let mut contract = create_contract();
+        seed_reward_pool(&mut contract, 5000);
+        contract.internal_grant_role(Role::RewardDistributor, &dex().into());
+        verify_players(&mut contract, &[carol()]);
+
+        let registration_cost = contract.storage_balance_bounds().min.0;
+        testing_env!(get_context(dex().to_string(), registration_cost));
+        contract.storage_deposit(Some(carol()));
+        contract.transfer_player_reward(carol().to_string(), vec![0], U64::from(0));
+    }
+
+    #[test]
+    fn storage_unregister_removes_reward_records() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+        let mut contract = create_contract();
+        seed_reward_pool(&mut contract, 5000);
+        contract.internal_grant_role(Role::RewardDistributor, &dex().into());
+        verify_players(&mut contract, &[bob(), carol()]);
+        contract.set_season("S1".to_string(), None, None);
+        contract.set_objective_schedule(1, None, None, Some("S1".to_string()));
+
+        // Season ranking is shared by the players of the Season, so it is kept
+        contract.storage_deposit(Some(bob()));
+        contract.transfer_player_reward(bob().to_string(), vec![1], U64::from(0));
+
+        let initial_storage_usage = env::storage_usage();
+        contract.storage_deposit(Some(carol()));
+        contract.transfer_player_reward(carol().to_string(), vec![0, 1], U64::from(0));
+        let used_storage = env::storage_usage() - initial_storage_usage;
+
+        testing_env!(get_context(carol().to_string(), ONE_YOCTO));
+        let initial_storage_usage = env::storage_usage();
+        assert!(contract.storage_unregister(Some(true)));
+
+        // Contract doesn't keep paying for the records of the unregistered account
+        assert_eq!(initial_storage_usage - env::storage_usage(), used_storage);
+        assert!(contract
+            .get_player_objectives(carol().to_string())
+            .is_empty());
+        assert_eq!(contract.get_reward_nonce(carol().to_string()), U64::from(0));
+        assert_eq!(contract.get_leaderboard(0, 10).len(), 1);
+        assert_eq!(
+            contract
+                .get_season_leaderboard("S1".to_string(), 0, 10)
+                .len(),
+            1
+        );
     }
 }
@@ -0,0 +1,145 @@
+use crate::*;
+
+impl FungibleToken {
+    /// Creates new tokens in the account, the total supply can't go beyond max_supply
+    pub fn internal_mint(&mut self, account_id: &AccountId, amount: Balance) {
+        require!(amount > 0, "The amount should be a positive number");
+
+        let new_supply = self
+            .total_supply
+            .checked_add(amount)
+            .unwrap_or_else(|| env::panic(b"Total supply overflow"));
+
+        self.assert_can_receive(account_id);
+
+        if let Some(max_supply) = self.max_supply {
+            require!(new_supply <= max_supply, "Max supply exceeded");
+        }
+
+        self.internal_deposit(account_id, amount);
+        self.total_supply = new_supply;
+    }
+
+    /// Destroys tokens of the account, reducing the total supply
+    pub fn internal_burn(&mut self, account_id: &AccountId, amount: Balance) {
+        require!(amount > 0, "The amount should be a positive number");
+        self.assert_can_send(account_id);
+
+        self.internal_withdraw(account_id, amount);
+        self.total_supply = self
+            .total_supply
+            .checked_sub(amount)
+            .unwrap_or_else(|| env::panic(b"Invariant : Total supply underflow"));
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Admin Only - Mint new tokens to a registered account, up to the max supply set at init
+    pub fn ft_mint(&mut self, account_id: ValidAccountId, amount: U128, memo: Option<String>) {
+        self.assert_role(Role::Admin);
+
+        let account_id: AccountId = account_id.into();
+
+        self.token.internal_mint(&account_id, amount.into());
+
+        FtMintLog {
+            owner_id: account_id,
+            amount,
+            memo,
+        }
+        .emit();
+    }
+
+    /// Burn tokens from the caller's balance
+    ///
+    /// Wallet UX Security -> Attach 1 Yocto
+    #[payable]
+    pub fn ft_burn(&mut self, amount: U128, memo: Option<String>) {
+        assert_one_yocto();
+        self.assert_not_paused(PauseFlag::Transfers);
+
+        let account_id = env::predecessor_account_id();
+
+        self.token.internal_burn(&account_id, amount.into());
+
+        FtBurnLog {
+            owner_id: account_id,
+            amount,
+            memo,
+        }
+        .emit();
+    }
+
+    /// View Function - returns the tokens which can still be minted, None if the supply is uncapped
+    pub fn ft_mintable_supply(&self) -> Option<U128> {
+        self.token
+            .max_supply
+            .map(|max_supply| U128::from(max_supply - self.token.total_supply))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod supply_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::Balance;
+    use near_sdk::MockedBlockchain;
+
+    fn create_capped_contract(max_supply: Balance) -> Contract {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.token.max_supply = Some(max_supply);
+        contract.storage_deposit(Some(carol()));
+
+        contract
+    }
+
+    #[test]
+    fn ft_mint_and_burn() {
+        let mut contract = create_capped_contract(1_000_000_000_001_000);
+
+        contract.ft_mint(carol(), U128::from(600), None);
+        assert_eq!(contract.ft_balance_of(carol()).0, 600);
+        assert_eq!(contract.ft_total_supply().0, 1_000_000_000_000_600);
+        assert_eq!(contract.ft_mintable_supply(), Some(U128::from(400)));
+
+        testing_env!(get_context(carol().to_string(), ONE_YOCTO));
+        contract.ft_burn(U128::from(100), Some("burn".to_string()));
+        assert_eq!(contract.ft_balance_of(carol()).0, 500);
+        assert_eq!(contract.ft_total_supply().0, 1_000_000_000_000_500);
+        assert_eq!(contract.ft_mintable_supply(), Some(U128::from(500)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Max supply exceeded")]
+    fn ft_mint_beyond_max_supply() {
+        let mut contract = create_capped_contract(1_000_000_000_001_000);
+
+        contract.ft_mint(carol(), U128::from(1001), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Total supply overflow")]
+    fn ft_mint_overflow() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        assert_eq!(contract.ft_mintable_supply(), None);
+
+        contract.ft_mint(dex(), U128::from(u128::MAX), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only accounts with Admin role can call this method")]
+    fn ft_mint_not_admin() {
+        let mut contract = create_capped_contract(1_000_000_000_001_000);
+
+        testing_env!(get_context(carol().to_string(), 0));
+        contract.ft_mint(carol(), U128::from(1), None);
+    }
+}
@@ -0,0 +1,413 @@
+use crate::*;
+
+/// 7 Days in nanoseconds
+const DEFAULT_EPOCH_DURATION: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Tokens set aside for Objective rewards along with the emission budgets
+///
+/// Tokens in the pool are part of the balance of the contract account, along with the unclaimed campaign budgets,
+/// the locked vesting grants and the sponsorship escrows and proposals, so that every payout is a real transfer from it
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct RewardTreasury {
+    /// Tokens available for rewards
+    pub balance: Balance,
+
+    /// Duration of an emission epoch in nanoseconds
+    pub epoch_duration: u64,
+
+    /// Max tokens which can be emitted as rewards in an epoch, None means no cap
+    pub epoch_budget: Option<Balance>,
+
+    /// Epoch for which epoch_emitted is being tracked
+    pub current_epoch: u64,
+
+    /// Tokens emitted as rewards in the current epoch
+    pub epoch_emitted: Balance,
+
+    /// Objective -> Remaining emission budget, Objectives without a budget are uncapped
+    pub objective_budgets: LookupMap<ObjectiveId, Balance>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardPoolView {
+    pub balance: U128,
+    pub epoch_duration: U64,
+    pub epoch_budget: Option<U128>,
+    pub current_epoch: U64,
+
+    /// Tokens which can still be emitted in the current epoch, None means no cap
+    pub epoch_remaining: Option<U128>,
+}
+
+impl Default for RewardTreasury {
+    fn default() -> Self {
+        Self {
+            balance: 0,
+            epoch_duration: DEFAULT_EPOCH_DURATION,
+            epoch_budget: None,
+            current_epoch: 0,
+            epoch_emitted: 0,
+            objective_budgets: LookupMap::new(StorageKey::ObjectiveBudgets.try_to_vec().unwrap()),
+        }
+    }
+}
+
+impl RewardTreasury {
+    fn epoch_at(&self, timestamp: u64) -> u64 {
+        timestamp / self.epoch_duration
+    }
+
+    /// Tokens emitted in the epoch of the current block
+    fn emitted_now(&self) -> Balance {
+        if self.epoch_at(env::block_timestamp()) == self.current_epoch {
+            self.epoch_emitted
+        } else {
+            0
+        }
+    }
+
+    pub fn epoch_remaining(&self) -> Option<Balance> {
+        self.epoch_budget
+            .map(|budget| budget.saturating_sub(self.emitted_now()))
+    }
+
+    /// Returns the exhausted budget if the reward is more than what is left of the budget of the Objective
+    pub fn check_objective_budget(
+        &self,
+        obj_id: ObjectiveId,
+        amount: Balance,
+    ) -> Result<(), RewardBudgetExhaustedLog> {
+        match self.objective_budgets.get(&obj_id) {
+            Some(remaining) if remaining < amount => Err(RewardBudgetExhaustedLog {
+                budget: "objective".to_string(),
+                obj_id: Some(obj_id),
+                requested: U128::from(amount),
+                remaining: U128::from(remaining),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the exhausted budget if the tokens can't be taken out of the pool
+    pub fn check_spend(&self, amount: Balance) -> Result<(), RewardBudgetExhaustedLog> {
+        if let Some(remaining) = self.epoch_remaining() {
+            if remaining < amount {
+                return Err(RewardBudgetExhaustedLog {
+                    budget: "epoch".to_string(),
+                    obj_id: None,
+                    requested: U128::from(amount),
+                    remaining: U128::from(remaining),
+                });
+            }
+        }
+
+        if self.balance < amount {
+            return Err(RewardBudgetExhaustedLog {
+                budget: "pool".to_string(),
+                obj_id: None,
+                requested: U128::from(amount),
+                remaining: U128::from(self.balance),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Deducts the reward of an Objective from its budget, if it has one
+    pub fn spend_objective_budget(&mut self, obj_id: ObjectiveId, amount: Balance) {
+        if let Err(exhausted) = self.check_objective_budget(obj_id, amount) {
+            env::panic(exhausted.reason().as_bytes());
+        }
+
+        if let Some(remaining) = self.objective_budgets.get(&obj_id) {
+            self.objective_budgets
+                .insert(&obj_id, &(remaining - amount));
+        }
+    }
+
+    /// Takes the tokens out of the pool while respecting the epoch budget
+    pub fn spend(&mut self, amount: Balance) {
+        if let Err(exhausted) = self.check_spend(amount) {
+            env::panic(exhausted.reason().as_bytes());
+        }
+
+        self.epoch_emitted = self.emitted_now() + amount;
+        self.current_epoch = self.epoch_at(env::block_timestamp());
+        self.balance -= amount;
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Move tokens from the caller's balance into the reward pool
+    ///
+    /// Wallet UX Security -> Attach 1 Yocto
+    #[payable]
+    pub fn fund_reward_pool(&mut self, amount: U128) {
+        assert_one_yocto();
+        self.assert_not_paused(PauseFlag::Transfers);
+
+        let account_id = env::predecessor_account_id();
+        let amount: Balance = amount.into();
+
+        require!(amount > 0, "The amount should be a positive number");
+        self.token.assert_can_send(&account_id);
+
+        self.token.internal_transfer(
+            &account_id,
+            &env::current_account_id(),
+            amount,
+            Some("reward pool".to_string()),
+        );
+        self.treasury.balance += amount;
+
+        RewardPoolFundLog {
+            account_id,
+            amount: U128::from(amount),
+            balance: U128::from(self.treasury.balance),
+        }
+        .emit();
+    }
+
+    /// Admin Only - Move tokens from the reward pool to the receiver's balance
+    pub fn withdraw_reward_pool(&mut self, receiver_id: ValidAccountId, amount: U128) {
+        self.assert_role(Role::Admin);
+        self.assert_not_paused(PauseFlag::Transfers);
+
+        let receiver_id: AccountId = receiver_id.into();
+        let amount: Balance = amount.into();
+
+        self.token.assert_can_receive(&receiver_id);
+        require!(
+            self.treasury.balance >= amount,
+            "Reward pool doesn't have enough balance"
+        );
+
+        self.treasury.balance -= amount;
+        self.token.internal_transfer(
+            &env::current_account_id(),
+            &receiver_id,
+            amount,
+            Some("reward pool".to_string()),
+        );
+
+        RewardPoolWithdrawLog {
+            receiver_id,
+            amount: U128::from(amount),
+            balance: U128::from(self.treasury.balance),
+            sender_id: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    /// Admin Only - Set the epoch duration in nanoseconds and the max tokens emitted per epoch, None removes the cap
+    pub fn set_epoch_budget(&mut self, epoch_duration: U64, epoch_budget: Option<U128>) {
+        self.assert_role(Role::Admin);
+
+        let epoch_duration: u64 = epoch_duration.into();
+        require!(epoch_duration > 0, "Epoch duration should be positive");
+
+        let emitted = self.treasury.emitted_now();
+
+        self.treasury.epoch_duration = epoch_duration;
+        self.treasury.epoch_budget = epoch_budget.map(|budget| budget.into());
+
+        // Tokens emitted so far are carried over to the epoch as per the new duration
+        self.treasury.current_epoch = self.treasury.epoch_at(env::block_timestamp());
+        self.treasury.epoch_emitted = emitted;
+
+        EpochBudgetUpdateLog {
+            epoch_duration: U64::from(epoch_duration),
+            epoch_budget,
+            sender_id: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    /// Admin Only - Set the remaining emission budget of an Objective, None removes the cap
+    pub fn set_objective_budget(&mut self, obj_id: ObjectiveId, budget: Option<U128>) {
+        self.assert_role(Role::Admin);
+        self.catch_objectives.get_objective(obj_id);
+
+        match budget {
+            Some(budget) => {
+                self.treasury
+                    .objective_budgets
+                    .insert(&obj_id, &budget.into());
+            }
+            None => {
+                self.treasury.objective_budgets.remove(&obj_id);
+            }
+        }
+
+        ObjectiveBudgetUpdateLog {
+            obj_id,
+            budget,
+            sender_id: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    /// View Function - returns the reward pool balance and the remaining epoch budget
+    pub fn get_reward_pool(&self) -> RewardPoolView {
+        RewardPoolView {
+            balance: U128::from(self.treasury.balance),
+            epoch_duration: U64::from(self.treasury.epoch_duration),
+            epoch_budget: self.treasury.epoch_budget.map(U128::from),
+            current_epoch: U64::from(self.treasury.epoch_at(env::block_timestamp())),
+            epoch_remaining: self.treasury.epoch_remaining().map(U128::from),
+        }
+    }
+
+    /// View Function - returns the remaining emission budget of an Objective, None means uncapped
+    pub fn get_objective_budget(&self, obj_id: ObjectiveId) -> Option<U128> {
+        self.treasury.objective_budgets.get(&obj_id).map(U128::from)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod treasury_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    fn reward_carol(contract: &mut Contract, obj_ids: Vec<ObjectiveId>, block_timestamp: u64) {
+        at(nft(), 0, block_timestamp);
+
+        let nonce = contract.get_reward_nonce(carol().to_string());
+        contract.transfer_player_reward(carol().to_string(), obj_ids, nonce);
+    }
+
+    #[test]
+    fn fund_reward_pool() {
+        testing_env!(get_context(dex().to_string(), ONE_YOCTO));
+
+        let mut contract = create_contract();
+        let total_supply = contract.ft_total_supply().0;
+
+        testing_env!(get_context(dex().to_string(), ONE_YOCTO));
+        contract.fund_reward_pool(U128::from(5000));
+
+        assert_eq!(contract.get_reward_pool().balance.0, 5000);
+        assert_eq!(contract.ft_balance_of(dex()).0, total_supply - 5000);
+        assert_eq!(contract.ft_balance_of(mike()).0, 5000);
+        assert_eq!(contract.ft_total_supply().0, total_supply);
+        assert_eq!(
+            near_sdk::test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":{"old_owner_id":"dex.near","new_owner_id":"mike.near","amount":"5000","memo":"reward pool"}}"#
+        );
+    }
+
+    #[test]
+    fn rewards_are_paid_from_pool() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        let owner_balance = contract.ft_balance_of(dex()).0;
+
+        reward_carol(&mut contract, vec![0, 1], 0);
+
+        assert_eq!(contract.ft_balance_of(carol()).0, 3000);
+        assert_eq!(contract.ft_balance_of(dex()).0, owner_balance);
+        assert_eq!(contract.get_reward_pool().balance.0, 2000);
+        assert_eq!(contract.ft_balance_of(mike()).0, 2000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Reward pool doesn't have enough balance")]
+    fn reward_pool_exhausted() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 2999);
+
+        reward_carol(&mut contract, vec![0, 1], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Epoch reward budget exhausted")]
+    fn epoch_budget_exhausted() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+        contract.set_epoch_budget(U64::from(100), Some(U128::from(1500)));
+
+        reward_carol(&mut contract, vec![0], 0);
+        assert_eq!(
+            contract.get_reward_pool().epoch_remaining,
+            Some(U128::from(500))
+        );
+
+        reward_carol(&mut contract, vec![1], 99);
+    }
+
+    #[test]
+    fn epoch_budget_resets() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+        contract.set_epoch_budget(U64::from(100), Some(U128::from(2000)));
+
+        reward_carol(&mut contract, vec![0], 0);
+        reward_carol(&mut contract, vec![1], 100);
+
+        assert_eq!(
+            contract.get_reward_pool().epoch_remaining,
+            Some(U128::from(0))
+        );
+        assert_eq!(contract.ft_balance_of(carol()).0, 3000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Objective reward budget exhausted")]
+    fn objective_budget_exhausted() {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+        contract.set_objective_budget(1, Some(U128::from(1999)));
+
+        reward_carol(&mut contract, vec![1], 0);
+    }
+
+    #[test]
+    fn treasury_admin_events() {
+        testing_env!(get_context(dex().to_string(), 0));
+
+        let mut contract = create_contract();
+        seed_reward_pool(&mut contract, 5000);
+
+        testing_env!(get_context(dex().to_string(), 0));
+        contract.withdraw_reward_pool(dex(), U128::from(1000));
+        contract.set_epoch_budget(U64::from(100), Some(U128::from(1500)));
+        contract.set_objective_budget(1, None);
+
+        let logs = near_sdk::test_utils::get_logs();
+        assert_eq!(
+            logs[1..],
+            [
+                r#"EVENT_JSON:{"standard":"catch","version":"1.0.0","event":"reward_pool_withdraw","data":{"receiver_id":"dex.near","amount":"1000","balance":"4000","sender_id":"dex.near"}}"#,
+                r#"EVENT_JSON:{"standard":"catch","version":"1.0.0","event":"epoch_budget_update","data":{"epoch_duration":"100","epoch_budget":"1500","sender_id":"dex.near"}}"#,
+                r#"EVENT_JSON:{"standard":"catch","version":"1.0.0","event":"objective_budget_update","data":{"obj_id":1,"budget":null,"sender_id":"dex.near"}}"#,
+            ]
+        );
+    }
+}
@@ -1,5 +1,3 @@
-use crate::Contract;
-
 #[macro_export]
 macro_rules! require {
     ( $a:expr, $b:expr ) => {
@@ -18,16 +16,48 @@ pub(crate) fn is_valid_username(username: &str) -> bool {
     }
 
     // ToDo -> Also check if the prefix is not a reserved keyword like users,settings,dao.....
-    return true;
+    true
 }
 
-impl Contract {
-    pub fn assert_owner(&self) {
-        require!(
-            near_sdk::env::predecessor_account_id() == self.owner_id,
-            "Only Owner of the Contract can call this method"
-        );
+/// Returns a * b / c rounded down, the product is computed in 256 bits so it can't overflow
+///
+/// Panics if the result doesn't fit in u128, it always does when b <= c
+pub(crate) fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    require!(c > 0, "Division by zero");
+
+    if let Some(product) = a.checked_mul(b) {
+        return product / c;
+    }
+
+    // a * b = hi * 2^128 + lo, from the products of the 64 bit halves
+    const LOW: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & LOW);
+    let (b_hi, b_lo) = (b >> 64, b & LOW);
+
+    let lo_lo = a_lo * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_lo = a_hi * b_lo;
+    let mid = (lo_lo >> 64) + (lo_hi & LOW) + (hi_lo & LOW);
+
+    let lo = (lo_lo & LOW) | (mid << 64);
+    let hi = a_hi * b_hi + (lo_hi >> 64) + (hi_lo >> 64) + (mid >> 64);
+    require!(hi < c, "Result doesn't fit in u128");
+
+    // Long division of the 256 bit product, one bit at a time
+    let mut quotient: u128 = 0;
+    let mut remainder = hi;
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((lo >> bit) & 1);
+        quotient <<= 1;
+
+        if carry == 1 || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            quotient |= 1;
+        }
     }
+
+    quotient
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -38,6 +68,11 @@ pub mod test_utils {
     use near_sdk::json_types::Base64VecU8;
     use near_sdk::Balance;
     use near_sdk::VMContext;
+    use near_sdk::{testing_env, MockedBlockchain};
+    use std::collections::HashMap;
+
+    pub const ONE_YOCTO: Balance = 1;
+    pub const STORAGE_COST: Balance = 100_000_000_000_000_000_000_000; // 1 Near = 10^24 Yocto Near
 
     // Helper functions
 
@@ -56,6 +91,10 @@ pub mod test_utils {
     pub fn nft() -> ValidAccountId {
         ValidAccountId::try_from("nft.catchlabs.near").unwrap()
     }
+    /// Account the contract is deployed on, current_account_id of get_context()
+    pub fn mike() -> ValidAccountId {
+        ValidAccountId::try_from("mike.near").unwrap()
+    }
 
     pub fn get_context(predecessor_account_id: AccountId, attached_deposit: Balance) -> VMContext {
         VMContext {
@@ -78,6 +117,13 @@ pub mod test_utils {
         }
     }
 
+    /// Sets up the context of a call made by the account with the deposit at the block timestamp
+    pub fn at(account_id: ValidAccountId, deposit: Balance, block_timestamp: u64) {
+        let mut context = get_context(account_id.to_string(), deposit);
+        context.block_timestamp = block_timestamp;
+        testing_env!(context);
+    }
+
     pub fn create_contract() -> Contract {
         let metadata = FungibleTokenMetadata {
             spec: String::from("1.1.0"),
@@ -91,6 +137,71 @@ pub mod test_utils {
             decimals: 0,
         };
         let total_supply = U128::from(1_000_000_000_000_000);
-        Contract::new(dex(), total_supply, metadata)
+        let mut contract = Contract::new(dex(), total_supply, metadata, None);
+        contract.internal_grant_role(Role::RewardDistributor, &nft().into());
+        contract
+    }
+
+    /// KYC verifies the players, so that they can be paid rewards
+    pub fn verify_players(contract: &mut Contract, player_ids: &[ValidAccountId]) {
+        for player_id in player_ids {
+            contract
+                .kyc
+                .set_record(player_id.as_ref(), KycStatus::Verified, 1, None);
+        }
+    }
+
+    /// Moves tokens from the owner's balance into the reward pool
+    pub fn seed_reward_pool(contract: &mut Contract, amount: Balance) {
+        contract.token.internal_withdraw(&dex().into(), amount);
+        contract
+            .token
+            .internal_deposit(&env::current_account_id(), amount);
+        contract.treasury.balance += amount;
+    }
+
+    /// Stand-in for the NFT Contract side of the reward protocol
+    #[derive(Default)]
+    pub struct MockNftContract {
+        /// Player -> Nonce to be used in the next reward call
+        pub nonces: HashMap<AccountId, u64>,
+
+        /// Player -> Achievements registered as rewarded
+        pub rewarded: HashMap<AccountId, Vec<ObjectiveId>>,
+    }
+
+    impl MockNftContract {
+        pub fn next_nonce(&self, player_id: &AccountId) -> U64 {
+            U64::from(*self.nonces.get(player_id).unwrap_or(&0))
+        }
+
+        /// Callback for transfer_player_reward(), registers the achievements as rewarded
+        /// only if the call succeeded and the RewardReceipt matches the request
+        pub fn on_reward_transferred(
+            &mut self,
+            player_id: AccountId,
+            obj_ids: Vec<ObjectiveId>,
+            nonce: U64,
+        ) -> bool {
+            let receipt = match env::promise_result(0) {
+                PromiseResult::Successful(value) => {
+                    near_sdk::serde_json::from_slice::<RewardReceipt>(&value).ok()
+                }
+                _ => None,
+            };
+
+            match receipt {
+                Some(receipt)
+                    if receipt.player_id == player_id
+                        && receipt.obj_ids == obj_ids
+                        && receipt.nonce == nonce =>
+                {
+                    self.nonces.insert(player_id.clone(), u64::from(nonce) + 1);
+                    self.rewarded.entry(player_id).or_default().extend(obj_ids);
+                    true
+                }
+                _ => false,
+            }
+        }
     }
 }
@@ -0,0 +1,481 @@
+use crate::*;
+
+/// Max grants which can be locked for an account at once, their storage is paid by the contract
+pub const MAX_VESTING_GRANTS: usize = 16;
+
+/// Vesting applied to the rewards of an Objective
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingSchedule {
+    /// Nanoseconds after the reward before which nothing can be claimed, equal to duration for a pure cliff
+    pub cliff: U64,
+
+    /// Nanoseconds after the reward over which it vests linearly
+    pub duration: U64,
+}
+
+impl VestingSchedule {
+    pub fn assert_valid_schedule(&self) {
+        require!(self.duration.0 > 0, "Vesting duration should be positive");
+        require!(
+            self.cliff.0 <= self.duration.0,
+            "Vesting cliff can't be greater than the duration"
+        );
+    }
+}
+
+/// Locked tokens of an account which vest as per a VestingSchedule
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct VestingGrant {
+    pub amount: Balance,
+
+    /// Tokens already moved to the account balance
+    pub released: Balance,
+
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+}
+
+impl VestingGrant {
+    /// Tokens vested at the timestamp, including the already released ones
+    fn vested_at(&self, timestamp: u64) -> Balance {
+        let elapsed = timestamp.saturating_sub(self.start);
+
+        if elapsed < self.cliff {
+            0
+        } else if elapsed >= self.duration {
+            self.amount
+        } else {
+            // Split to not overflow amount * elapsed
+            let (elapsed, duration) = (u128::from(elapsed), u128::from(self.duration));
+            self.amount / duration * elapsed + self.amount % duration * elapsed / duration
+        }
+    }
+
+    fn is_fully_vested(&self, timestamp: u64) -> bool {
+        self.vested_at(timestamp) == self.amount
+    }
+}
+
+/// Grant to which tokens locked at start as per the schedule are added instead of a new grant
+///
+/// Only rewards locked in the same block with the same schedule vest together, so that a reward
+/// never vests earlier than its own schedule
+fn merge_index(grants: &[VestingGrant], schedule: &VestingSchedule, start: u64) -> Option<usize> {
+    grants.iter().position(|grant| {
+        grant.start == start
+            && grant.cliff == schedule.cliff.0
+            && grant.duration == schedule.duration.0
+    })
+}
+
+/// Balances of an account, only the spendable ones can be transferred
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingBalance {
+    /// Same as ft_balance_of
+    pub spendable: U128,
+
+    /// Vested tokens which can be moved to the spendable balance with claim_vested()
+    pub claimable: U128,
+
+    /// Tokens which haven't vested yet
+    pub locked: U128,
+}
+
+impl FungibleToken {
+    /// Locks the tokens for the account as per the schedule, they are not part of the account balance until claimed
+    pub fn internal_lock(
+        &mut self,
+        account_id: &AccountId,
+        amount: Balance,
+        schedule: &VestingSchedule,
+    ) {
+        let mut grants = self.vesting.get(account_id).unwrap_or_default();
+        let start = env::block_timestamp();
+
+        if let Some(index) = merge_index(&grants, schedule, start) {
+            grants[index].amount += amount;
+            self.vesting.insert(account_id, &grants);
+            return;
+        }
+
+        if grants.len() >= MAX_VESTING_GRANTS {
+            // Fully vested grants are moved to the account balance to make room for the new one
+            let vested: Balance = grants
+                .iter()
+                .filter(|grant| grant.is_fully_vested(start))
+                .map(|grant| grant.amount - grant.released)
+                .sum();
+            grants.retain(|grant| !grant.is_fully_vested(start));
+
+            require!(
+                grants.len() < MAX_VESTING_GRANTS,
+                "Account has too many vesting grants"
+            );
+
+            if vested > 0 {
+                self.internal_transfer(
+                    &env::current_account_id(),
+                    account_id,
+                    vested,
+                    Some("vested".to_string()),
+                );
+            }
+        }
+
+        grants.push(VestingGrant {
+            amount,
+            released: 0,
+            start,
+            cliff: schedule.cliff.0,
+            duration: schedule.duration.0,
+        });
+        self.vesting.insert(account_id, &grants);
+    }
+
+    /// Moves the vested tokens of the account to its balance, returns the amount moved
+    pub fn internal_release_vested(&mut self, account_id: &AccountId) -> Balance {
+        let mut grants = match self.vesting.get(account_id) {
+            Some(grants) => grants,
+            None => return 0,
+        };
+
+        let now = env::block_timestamp();
+        let mut released = 0;
+
+        for grant in grants.iter_mut() {
+            let vested = grant.vested_at(now);
+            released += vested - grant.released;
+            grant.released = vested;
+        }
+
+        // Fully released grants are dropped to keep the ledger small
+        grants.retain(|grant| grant.released < grant.amount);
+
+        if grants.is_empty() {
+            self.vesting.remove(account_id);
+        } else {
+            self.vesting.insert(account_id, &grants);
+        }
+
+        if released > 0 {
+            self.internal_transfer(
+                &env::current_account_id(),
+                account_id,
+                released,
+                Some("vested".to_string()),
+            );
+        }
+
+        released
+    }
+
+    /// Returns false if internal_lock() would panic for one of the schedules, when they are locked in this order
+    pub fn can_lock(&self, account_id: &AccountId, schedules: &[&VestingSchedule]) -> bool {
+        let mut grants = self.vesting.get(account_id).unwrap_or_default();
+        let start = env::block_timestamp();
+
+        // Fully vested grants make room for new ones once the account is full
+        grants.retain(|grant| !grant.is_fully_vested(start));
+
+        schedules.iter().all(|schedule| {
+            if merge_index(&grants, schedule, start).is_some() {
+                return true;
+            }
+            if grants.len() >= MAX_VESTING_GRANTS {
+                return false;
+            }
+
+            grants.push(VestingGrant {
+                amount: 0,
+                released: 0,
+                start,
+                cliff: schedule.cliff.0,
+                duration: schedule.duration.0,
+            });
+            true
+        })
+    }
+
+    /// Removes every grant of the account, returns the tokens which were not released yet
+    pub fn internal_remove_vesting(&mut self, account_id: &AccountId) -> Balance {
+        self.vesting
+            .remove(account_id)
+            .unwrap_or_default()
+            .iter()
+            .map(|grant| grant.amount - grant.released)
+            .sum()
+    }
+
+    /// Returns the claimable and locked tokens of the account
+    pub fn vesting_balance_of(&self, account_id: &AccountId) -> (Balance, Balance) {
+        let now = env::block_timestamp();
+
+        self.vesting
+            .get(account_id)
+            .unwrap_or_default()
+            .iter()
+            .fold((0, 0), |(claimable, locked), grant| {
+                let vested = grant.vested_at(now);
+                (
+                    claimable + vested - grant.released,
+                    locked + grant.amount - vested,
+                )
+            })
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Move the vested rewards of the caller to their spendable balance, returns the amount moved
+    pub fn claim_vested(&mut self) -> U128 {
+        self.assert_not_paused(PauseFlag::Rewards);
+
+        let account_id = env::predecessor_account_id();
+        self.token.assert_can_receive(&account_id);
+
+        let released = self.token.internal_release_vested(&account_id);
+        require!(released > 0, "Nothing to claim");
+
+        U128::from(released)
+    }
+
+    /// MetadataManager Only - Set the vesting applied to the rewards of an Objective, None pays them right away
+    pub fn set_objective_vesting(&mut self, obj_id: ObjectiveId, vesting: Option<VestingSchedule>) {
+        self.assert_role(Role::MetadataManager);
+
+        let mut objective = self.catch_objectives.get_objective(obj_id);
+
+        if let Some(vesting) = &vesting {
+            vesting.assert_valid_schedule();
+        }
+        objective.vesting = vesting;
+
+        self.catch_objectives
+            .objectives
+            .replace(obj_id.into(), &objective);
+
+        ObjectiveVestingUpdateLog {
+            obj_id,
+            vesting: objective.vesting,
+        }
+        .emit();
+    }
+
+    /// View Function - returns the tokens of the account which can't be transferred yet, claimable and locked both
+    pub fn ft_locked_balance_of(&self, account_id: ValidAccountId) -> U128 {
+        let (claimable, locked) = self.token.vesting_balance_of(account_id.as_ref());
+
+        U128::from(claimable + locked)
+    }
+
+    /// View Function - returns the spendable, claimable and locked tokens of the account
+    pub fn ft_vesting_balance_of(&self, account_id: ValidAccountId) -> VestingBalance {
+        let (claimable, locked) = self.token.vesting_balance_of(account_id.as_ref());
+
+        VestingBalance {
+            spendable: self.ft_balance_of(account_id),
+            claimable: U128::from(claimable),
+            locked: U128::from(locked),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod vesting_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    /// Objective1 vests over 100ns with a 20ns cliff, carol is rewarded both Objectives at 1000
+    fn create_vesting_contract() -> Contract {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        verify_players(&mut contract, &[carol()]);
+        seed_reward_pool(&mut contract, 5000);
+
+        contract.set_objective_vesting(
+            0,
+            Some(VestingSchedule {
+                cliff: U64::from(20),
+                duration: U64::from(100),
+            }),
+        );
+
+        at(nft(), 0, 1000);
+        contract.transfer_player_reward(carol().to_string(), vec![0, 1], U64::from(0));
+
+        contract
+    }
+
+    #[test]
+    fn vested_at() {
+        let grant = VestingGrant {
+            amount: 1000,
+            released: 0,
+            start: 100,
+            cliff: 20,
+            duration: 100,
+        };
+
+        assert_eq!(grant.vested_at(119), 0);
+        assert_eq!(grant.vested_at(120), 200);
+        assert_eq!(grant.vested_at(150), 500);
+        assert_eq!(grant.vested_at(300), 1000);
+    }
+
+    #[test]
+    fn rewards_merge_in_same_block() {
+        let mut contract = create_vesting_contract();
+        let schedule = VestingSchedule {
+            cliff: U64::from(20),
+            duration: U64::from(100),
+        };
+        let carol_id: AccountId = carol().into();
+
+        at(nft(), 0, 1000);
+        contract.token.internal_lock(&carol_id, 500, &schedule);
+        assert_eq!(contract.token.vesting.get(&carol_id).unwrap().len(), 1);
+
+        // A later reward gets its own grant and stays locked while the earlier one vests
+        at(nft(), 0, 1090);
+        contract.token.internal_lock(&carol_id, 500, &schedule);
+
+        let grants = contract.token.vesting.get(&carol_id).unwrap();
+        assert_eq!(grants.len(), 2);
+        assert_eq!(grants[0].amount, 1500);
+        assert_eq!(grants[1].start, 1090);
+
+        let (claimable, locked) = contract.token.vesting_balance_of(&carol_id);
+        assert_eq!(claimable, 1350);
+        assert_eq!(locked, 150 + 500);
+    }
+
+    #[test]
+    fn grants_are_capped() {
+        let mut contract = create_vesting_contract();
+        let schedule = VestingSchedule {
+            cliff: U64::from(0),
+            duration: U64::from(100),
+        };
+        let carol_id: AccountId = carol().into();
+
+        for i in 1..MAX_VESTING_GRANTS as u64 {
+            at(nft(), 0, 1000 + i);
+            contract.token.internal_lock(&carol_id, 10, &schedule);
+        }
+        assert_eq!(contract.token.vesting.get(&carol_id).unwrap().len(), 16);
+
+        // Nothing has fully vested yet, a reward of a later block can't be merged into an older grant
+        at(nft(), 0, 1050);
+        assert!(!contract.token.can_lock(&carol_id, &[&schedule]));
+
+        // Grant of the first reward has fully vested and moves to the balance
+        at(nft(), 0, 1100);
+        let new_schedule = VestingSchedule {
+            cliff: U64::from(0),
+            duration: U64::from(50),
+        };
+        assert!(contract.token.can_lock(&carol_id, &[&schedule, &schedule]));
+        assert!(!contract
+            .token
+            .can_lock(&carol_id, &[&schedule, &new_schedule]));
+        contract.token.internal_lock(&carol_id, 10, &schedule);
+
+        let grants = contract.token.vesting.get(&carol_id).unwrap();
+        assert_eq!(grants.len(), 16);
+        assert_eq!(grants[15].start, 1100);
+        assert_eq!(grants[15].amount, 10);
+        assert_eq!(contract.ft_balance_of(carol()).0, 3000);
+
+        // New reward is locked in full next to what is left of the older grants
+        let (_, locked) = contract.token.vesting_balance_of(&carol_id);
+        assert_eq!(locked, 20 + 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account has too many vesting grants")]
+    fn grants_cap_reached() {
+        let mut contract = create_vesting_contract();
+        let schedule = VestingSchedule {
+            cliff: U64::from(0),
+            duration: U64::from(100),
+        };
+        let carol_id: AccountId = carol().into();
+
+        for i in 1..=MAX_VESTING_GRANTS as u64 {
+            at(nft(), 0, 1000 + i);
+            contract.token.internal_lock(&carol_id, 10, &schedule);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Account carol.near is frozen for incoming transfers")]
+    fn claim_vested_frozen() {
+        let mut contract = create_vesting_contract();
+
+        at(dex(), 0, 1100);
+        contract.freeze_account(carol(), FreezeDirection::Incoming, "LR-017".to_string());
+
+        at(carol(), 0, 1100);
+        contract.claim_vested();
+    }
+
+    #[test]
+    fn rewards_are_locked() {
+        let contract = create_vesting_contract();
+
+        assert_eq!(contract.ft_balance_of(carol()).0, 2000);
+        assert_eq!(contract.ft_locked_balance_of(carol()).0, 1000);
+        assert_eq!(
+            contract.ft_vesting_balance_of(carol()),
+            VestingBalance {
+                spendable: U128::from(2000),
+                claimable: U128::from(0),
+                locked: U128::from(1000),
+            }
+        );
+    }
+
+    #[test]
+    fn claim_vested() {
+        let mut contract = create_vesting_contract();
+
+        at(carol(), 0, 1050);
+        assert_eq!(contract.claim_vested(), U128::from(500));
+        assert_eq!(contract.ft_balance_of(carol()).0, 2500);
+
+        at(carol(), 0, 2000);
+        assert_eq!(contract.claim_vested(), U128::from(500));
+        assert_eq!(contract.ft_balance_of(carol()).0, 3000);
+        assert_eq!(contract.ft_locked_balance_of(carol()).0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Nothing to claim")]
+    fn claim_before_cliff() {
+        let mut contract = create_vesting_contract();
+
+        at(carol(), 0, 1019);
+        contract.claim_vested();
+    }
+
+    #[test]
+    #[should_panic(expected = "The account doesn't have enough balance")]
+    fn locked_tokens_cant_be_transferred() {
+        let mut contract = create_vesting_contract();
+
+        at(dex(), STORAGE_COST, 1000);
+        contract.storage_deposit(Some(alice()));
+
+        at(carol(), ONE_YOCTO, 1000);
+        contract.ft_transfer(alice(), U128::from(2001), None);
+    }
+}
@@ -0,0 +1,426 @@
+use crate::*;
+
+use ed25519_dalek::{PublicKey, Signature};
+use std::convert::TryFrom;
+
+/// Reward for a single Objective signed by the game server, claimed by the player
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardVoucher {
+    pub player_id: AccountId,
+    pub obj_id: ObjectiveId,
+    pub amount: U128,
+
+    /// Has to be unique per player, vouchers can be claimed in any order
+    pub nonce: U64,
+
+    /// Block timestamp in nanoseconds after which the voucher can't be claimed
+    pub expires_at: U64,
+}
+
+/// Message signed by the game server, the contract account is part of it so that a voucher is valid only for this contract
+#[derive(BorshSerialize)]
+struct VoucherMessage<'a> {
+    contract_id: &'a AccountId,
+    player_id: &'a AccountId,
+    obj_id: ObjectiveId,
+    amount: Balance,
+    nonce: u64,
+    expires_at: u64,
+}
+
+impl RewardVoucher {
+    /// Borsh serialized VoucherMessage
+    pub fn message(&self) -> Vec<u8> {
+        VoucherMessage {
+            contract_id: &env::current_account_id(),
+            player_id: &self.player_id,
+            obj_id: self.obj_id,
+            amount: self.amount.0,
+            nonce: self.nonce.0,
+            expires_at: self.expires_at.0,
+        }
+        .try_to_vec()
+        .unwrap()
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct VoucherRegistry {
+    /// ed25519 public keys trusted to sign vouchers
+    pub keys: UnorderedSet<Base58PublicKey>,
+
+    /// Player -> Vouchers claimed by the player
+    pub claimed: LookupMap<AccountId, ClaimedVouchers>,
+}
+
+/// Vouchers claimed by a player, removed when the player unregisters
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ClaimedVouchers {
+    pub nonces: UnorderedSet<u64>,
+
+    /// Latest expires_at of the claimed vouchers, the player can't unregister before it so that they can't be claimed again
+    pub expires_at: u64,
+}
+
+impl Default for VoucherRegistry {
+    fn default() -> Self {
+        Self {
+            keys: UnorderedSet::new(StorageKey::VoucherKeys.try_to_vec().unwrap()),
+            claimed: LookupMap::new(StorageKey::VoucherNonces.try_to_vec().unwrap()),
+        }
+    }
+}
+
+impl VoucherRegistry {
+    /// Records the nonce of the claimed voucher, returns false if it was already claimed
+    pub fn record_claim(&mut self, player_id: &AccountId, nonce: u64, expires_at: u64) -> bool {
+        let mut claimed = self
+            .claimed
+            .get(player_id)
+            .unwrap_or_else(|| ClaimedVouchers {
+                nonces: UnorderedSet::new(
+                    StorageKey::PlayerVoucherNonces {
+                        account_hash: env::sha256(player_id.as_bytes()),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                ),
+                expires_at: 0,
+            });
+
+        if !claimed.nonces.insert(&nonce) {
+            return false;
+        }
+        claimed.expires_at = claimed.expires_at.max(expires_at);
+
+        self.claimed.insert(player_id, &claimed);
+        true
+    }
+
+    pub fn is_claimed(&self, player_id: &AccountId, nonce: u64) -> bool {
+        self.claimed
+            .get(player_id)
+            .is_some_and(|claimed| claimed.nonces.contains(&nonce))
+    }
+
+    /// Block timestamp from which none of the vouchers claimed by the player can be claimed anymore
+    pub fn claims_expire_at(&self, player_id: &AccountId) -> u64 {
+        self.claimed
+            .get(player_id)
+            .map_or(0, |claimed| claimed.expires_at)
+    }
+
+    /// Removes the claimed vouchers of the player
+    pub fn remove_player(&mut self, player_id: &AccountId) {
+        if let Some(mut claimed) = self.claimed.remove(player_id) {
+            claimed.nonces.clear();
+        }
+    }
+}
+
+fn assert_ed25519_key(public_key: &Base58PublicKey) {
+    require!(
+        public_key.0.len() == 33 && public_key.0[0] == 0,
+        "Only ed25519 keys are supported"
+    );
+}
+
+/// Verifies the ed25519 signature of the message, public_key is a Base58PublicKey with the curve type prefix
+fn verify_signature(public_key: &Base58PublicKey, message: &[u8], signature: &[u8]) -> bool {
+    let public_key = match PublicKey::from_bytes(&public_key.0[1..]) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+
+    match Signature::try_from(signature) {
+        Ok(signature) => public_key.verify_strict(message, &signature).is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Admin Only - Trust the ed25519 key to sign reward vouchers
+    pub fn add_voucher_key(&mut self, public_key: Base58PublicKey) {
+        self.assert_role(Role::Admin);
+        assert_ed25519_key(&public_key);
+
+        require!(
+            self.vouchers.keys.insert(&public_key),
+            "Voucher key already exists"
+        );
+
+        VoucherKeyAddLog {
+            public_key,
+            sender_id: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    /// Admin Only - Stop trusting the key, vouchers signed by it can't be claimed anymore
+    pub fn remove_voucher_key(&mut self, public_key: Base58PublicKey) {
+        self.assert_role(Role::Admin);
+
+        require!(
+            self.vouchers.keys.remove(&public_key),
+            "Voucher key doesn't exist"
+        );
+
+        VoucherKeyRemoveLog {
+            public_key,
+            sender_id: env::predecessor_account_id(),
+        }
+        .emit();
+    }
+
+    /// Claim a reward voucher signed by one of the trusted keys, the caller has to be the player of the voucher
+    ///
+    /// public_key tells which of the trusted keys signed the voucher
+    pub fn claim_voucher(
+        &mut self,
+        voucher: RewardVoucher,
+        signature: Base64VecU8,
+        public_key: Base58PublicKey,
+    ) -> U128 {
+        let player_id = env::predecessor_account_id();
+
+        require!(
+            voucher.player_id == player_id,
+            "Voucher belongs to another player"
+        );
+        require!(
+            self.token.accounts.contains_key(&player_id),
+            "Player is Not Registered with the FT contract"
+        );
+        require!(
+            env::block_timestamp() < voucher.expires_at.0,
+            "Voucher has expired"
+        );
+        require!(
+            self.vouchers.keys.contains(&public_key),
+            "Voucher key is not trusted"
+        );
+        require!(
+            verify_signature(&public_key, &voucher.message(), &signature.0),
+            "Invalid voucher signature"
+        );
+        // Claimed voucher is recorded until the player unregisters, paid from the player's storage deposit
+        let initial_storage_usage = env::storage_usage();
+        require!(
+            self.vouchers
+                .record_claim(&player_id, voucher.nonce.0, voucher.expires_at.0),
+            "Voucher is already claimed"
+        );
+        self.token
+            .internal_use_storage(&player_id, env::storage_usage() - initial_storage_usage);
+
+        let amount =
+            self.internal_pay_objectives(&player_id, &[voucher.obj_id], Some(&[voucher.amount.0]));
+
+        VoucherClaimLog {
+            player_id,
+            obj_id: voucher.obj_id,
+            amount: U128::from(amount),
+            nonce: voucher.nonce,
+            public_key,
+        }
+        .emit();
+
+        U128::from(amount)
+    }
+
+    /// View Function - returns the keys trusted to sign vouchers
+    pub fn get_voucher_keys(&self) -> Vec<Base58PublicKey> {
+        self.vouchers.keys.to_vec()
+    }
+
+    /// View Function - returns true if the voucher nonce of the player is already used
+    pub fn is_voucher_claimed(&self, player_id: AccountId, nonce: U64) -> bool {
+        self.vouchers.is_claimed(&player_id, nonce.0)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod vouchers_tests {
+    use super::*;
+    use utils::test_utils::*;
+
+    use ed25519_dalek::{Keypair, SecretKey, Signer};
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    fn server_keypair(seed: u8) -> Keypair {
+        let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+        let public = PublicKey::from(&secret);
+
+        Keypair { secret, public }
+    }
+
+    fn base58_public_key(keypair: &Keypair) -> Base58PublicKey {
+        let mut key = vec![0];
+        key.extend_from_slice(keypair.public.as_bytes());
+
+        Base58PublicKey(key)
+    }
+
+    fn voucher(nonce: u64) -> RewardVoucher {
+        RewardVoucher {
+            player_id: carol().to_string(),
+            obj_id: 1,
+            amount: U128::from(500),
+            nonce: U64::from(nonce),
+            expires_at: U64::from(100),
+        }
+    }
+
+    fn sign(keypair: &Keypair, voucher: &RewardVoucher) -> Base64VecU8 {
+        Base64VecU8::from(keypair.sign(&voucher.message()).to_bytes().to_vec())
+    }
+
+    /// carol is registered but not KYC verified
+    fn create_unverified_voucher_contract() -> Contract {
+        testing_env!(get_context(dex().to_string(), STORAGE_COST));
+
+        let mut contract = create_contract();
+        contract.storage_deposit(Some(carol()));
+        seed_reward_pool(&mut contract, 5000);
+        contract.add_voucher_key(base58_public_key(&server_keypair(1)));
+
+        testing_env!(get_context(carol().to_string(), 0));
+
+        contract
+    }
+
+    fn create_voucher_contract() -> Contract {
+        let mut contract = create_unverified_voucher_contract();
+        verify_players(&mut contract, &[carol()]);
+
+        contract
+    }
+
+    #[test]
+    fn claim_voucher() {
+        let mut contract = create_voucher_contract();
+        let keypair = server_keypair(1);
+
+        let voucher = voucher(7);
+        let signature = sign(&keypair, &voucher);
+
+        contract.claim_voucher(voucher, signature, base58_public_key(&keypair));
+
+        assert_eq!(contract.ft_balance_of(carol()).0, 500);
+        assert!(contract.is_voucher_claimed(carol().to_string(), U64::from(7)));
+        assert_eq!(
+            contract.get_player_objectives(carol().to_string())[0].obj_id,
+            1
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Account carol.near is not KYC verified")]
+    fn claim_voucher_unverified() {
+        let mut contract = create_unverified_voucher_contract();
+        let keypair = server_keypair(1);
+
+        let voucher = voucher(7);
+        let signature = sign(&keypair, &voucher);
+
+        contract.claim_voucher(voucher, signature, base58_public_key(&keypair));
+    }
+
+    #[test]
+    #[should_panic(expected = "Voucher is already claimed")]
+    fn claim_voucher_replay() {
+        let mut contract = create_voucher_contract();
+        let keypair = server_keypair(1);
+
+        let voucher = voucher(7);
+        let signature = sign(&keypair, &voucher);
+
+        contract.claim_voucher(
+            voucher.clone(),
+            signature.clone(),
+            base58_public_key(&keypair),
+        );
+        contract.claim_voucher(voucher, signature, base58_public_key(&keypair));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid voucher signature")]
+    fn claim_voucher_tampered() {
+        let mut contract = create_voucher_contract();
+        let keypair = server_keypair(1);
+
+        let mut voucher = voucher(7);
+        let signature = sign(&keypair, &voucher);
+        voucher.amount = U128::from(5000);
+
+        contract.claim_voucher(voucher, signature, base58_public_key(&keypair));
+    }
+
+    #[test]
+    #[should_panic(expected = "Voucher key is not trusted")]
+    fn claim_voucher_rotated_key() {
+        let mut contract = create_voucher_contract();
+        let old_keypair = server_keypair(1);
+        let new_keypair = server_keypair(2);
+
+        testing_env!(get_context(dex().to_string(), 0));
+        contract.add_voucher_key(base58_public_key(&new_keypair));
+        contract.remove_voucher_key(base58_public_key(&old_keypair));
+        assert_eq!(
+            contract.get_voucher_keys(),
+            vec![base58_public_key(&new_keypair)]
+        );
+
+        testing_env!(get_context(carol().to_string(), 0));
+        let voucher = voucher(7);
+        let signature = sign(&old_keypair, &voucher);
+
+        contract.claim_voucher(voucher, signature, base58_public_key(&old_keypair));
+    }
+
+    #[test]
+    #[should_panic(expected = "Voucher belongs to another player")]
+    fn claim_voucher_of_another_player() {
+        let mut contract = create_voucher_contract();
+        let keypair = server_keypair(1);
+
+        let voucher = voucher(7);
+        let signature = sign(&keypair, &voucher);
+
+        testing_env!(get_context(alice().to_string(), 0));
+        contract.claim_voucher(voucher, signature, base58_public_key(&keypair));
+    }
+
+    #[test]
+    fn storage_unregister_after_voucher_expiry() {
+        let mut contract = create_voucher_contract();
+        let keypair = server_keypair(1);
+
+        let voucher = voucher(7);
+        let signature = sign(&keypair, &voucher);
+        contract.claim_voucher(voucher, signature, base58_public_key(&keypair));
+
+        at(carol(), ONE_YOCTO, 100);
+        assert!(contract.storage_unregister(Some(true)));
+        assert!(!contract.is_voucher_claimed(carol().to_string(), U64::from(7)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't unregister the account before its claimed vouchers expire")]
+    fn storage_unregister_before_voucher_expiry() {
+        let mut contract = create_voucher_contract();
+        let keypair = server_keypair(1);
+
+        let voucher = voucher(7);
+        let signature = sign(&keypair, &voucher);
+        contract.claim_voucher(voucher, signature, base58_public_key(&keypair));
+
+        // Voucher could be claimed again if the account registers again
+        testing_env!(get_context(carol().to_string(), 1));
+        contract.storage_unregister(Some(true));
+    }
+}
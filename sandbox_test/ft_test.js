@@ -32,7 +32,8 @@ const contractMethods = {
     "storage_unregister",
     "ft_transfer",
     "ft_transfer_call",
-    "mint",
+    "ft_mint",
+    "ft_burn",
     "ft_transfer_player_reward",
     "new",
   ],
@@ -146,8 +147,8 @@ async function test() {
   // Test storage_balance_bounds()
 
   const exp_storage_balance_bounds = {
-    min: "1250000000000000000000",
-    max: "1250000000000000000000",
+    min: "11700000000000000000000",
+    max: null,
   };
 
   const storage_balance_bounds =